@@ -31,4 +31,121 @@ pub enum RewardError {
 
     #[msg("Reserve calculation error")]
     ReserveCalculationError,
-}
\ No newline at end of file
+
+    #[msg("Contribution already verified")]
+    ContributionAlreadyVerified,
+
+    #[msg("Account does not belong to this points config")]
+    ConfigMismatch,
+
+    #[msg("Moderator is suspended")]
+    ModeratorSuspended,
+
+    #[msg("Invalid moderator permissions")]
+    InvalidPermissions,
+
+    #[msg("Recorder list is full")]
+    RecorderListFull,
+
+    #[msg("Recorder already registered")]
+    RecorderAlreadyRegistered,
+
+    #[msg("Recorder not found")]
+    RecorderNotFound,
+
+    #[msg("No attestation agent registered")]
+    AttestationAgentNotSet,
+
+    #[msg("Ed25519 attestation instruction missing")]
+    MissingAttestation,
+
+    #[msg("Invalid attestation signature or payload")]
+    InvalidAttestation,
+
+    #[msg("Attestation nonce already used")]
+    AttestationReplayed,
+
+    #[msg("This artifact has already been recorded")]
+    DuplicateContribution,
+
+    #[msg("Monthly points cap reached for this contribution type")]
+    MonthlyTypeCapReached,
+
+    #[msg("Contribution type is disabled")]
+    ContributionTypeDisabled,
+
+    #[msg("Account address does not match the expected PDA")]
+    InvalidAccountAddress,
+
+    #[msg("Account already initialized")]
+    AccountAlreadyInitialized,
+
+    #[msg("Invalid contribution batch")]
+    InvalidBatch,
+
+    #[msg("No points to claim for this period")]
+    NothingToClaim,
+
+    #[msg("Merkle root already published for this period")]
+    MerkleRootAlreadySet,
+
+    #[msg("This period is paid through Merkle proofs")]
+    MerkleDistributionActive,
+
+    #[msg("Invalid Merkle distribution parameters")]
+    InvalidMerkleDistribution,
+
+    #[msg("Invalid Merkle proof")]
+    InvalidMerkleProof,
+
+    #[msg("Leaf already claimed")]
+    AlreadyClaimed,
+
+    #[msg("Claim window for this period has expired")]
+    ClaimWindowExpired,
+
+    #[msg("Claim window for this period is still open")]
+    ClaimWindowOpen,
+
+    #[msg("Reward pool is inactive")]
+    RewardPoolInactive,
+
+    #[msg("Reward vault cannot cover the scheduled emission")]
+    EmissionUnderfunded,
+
+    #[msg("Token account is not a payout destination for this contributor")]
+    InvalidPayoutDestination,
+
+    #[msg("Reserve withdrawal is still timelocked")]
+    WithdrawalTimelocked,
+
+    #[msg("Reserve withdrawal exceeds the per-period limit")]
+    WithdrawalLimitExceeded,
+
+    #[msg("Reserve withdrawal is no longer pending")]
+    WithdrawalNotPending,
+
+    #[msg("Vaults are already initialized")]
+    VaultsAlreadyInitialized,
+
+    #[msg("Account is already at the current layout")]
+    AccountAlreadyMigrated,
+
+    #[msg("Vesting escrow accounts are required while vesting locks part of the payout")]
+    VestingAccountsRequired,
+
+    #[msg("This period redistributes capped excess and is paid through Merkle proofs")]
+    MerkleDistributionRequired,
+
+    #[msg("No reserve withdrawal policy change is pending")]
+    PolicyChangeNotPending,
+
+    #[msg("Reserve withdrawal policy change is still timelocked")]
+    PolicyChangeTimelocked,
+
+    #[msg("Changing the reserve guardian requires the current guardian's signature")]
+    GuardianApprovalRequired,
+
+    #[msg("Token account has a delegate or close authority and cannot be adopted as a vault")]
+    VaultNotAdoptable,
+}
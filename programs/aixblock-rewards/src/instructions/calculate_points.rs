@@ -1,9 +1,16 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::{
     contributor::Contributor,
+    distribution::DistributionPeriod,
+    moderator::{Moderator, PERMISSION_CLOSE_PERIOD},
+    period_points::ContributorPeriodPoints,
     points::PointsConfig,
+    reserve::ReserveState,
 };
 use crate::errors::RewardError;
+use crate::instructions::record_contribution::load_period_points;
+use crate::utils::{transfer_checked_net, write_account};
 
 #[derive(Accounts)]
 pub struct CalculateMonthlyPoints<'info> {
@@ -11,25 +18,144 @@ pub struct CalculateMonthlyPoints<'info> {
     pub points_config: Account<'info, PointsConfig>,
 
     #[account(
-        mut,
-        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+        seeds = [
+            b"moderator",
+            points_config.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = moderator.bump,
+        constraint = !moderator.is_suspended @ RewardError::ModeratorSuspended,
+        constraint = moderator.has_permission(PERMISSION_CLOSE_PERIOD) @ RewardError::Unauthorized,
     )]
+    pub moderator: Account<'info, Moderator>,
+
+    #[account(mut)]
     pub authority: Signer<'info>,
 
-    /// CHECK: Used for PDA derivation
-    pub distribution_account: AccountInfo<'info>,
+    // Created once per period at close; contributors claim against it
+    #[account(
+        init,
+        payer = authority,
+        space = DistributionPeriod::SPACE,
+        seeds = [
+            b"distribution",
+            points_config.key().as_ref(),
+            &points_config.current_period.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub distribution_period: Account<'info, DistributionPeriod>,
+
+    #[account(address = points_config.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = points_config.reward_vault)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for reward vault authority
+    #[account(
+        seeds = [b"vault_authority", points_config.key().as_ref()],
+        bump = points_config.vault_authority_bump
+    )]
+    pub reward_vault_authority: AccountInfo<'info>,
+
+    #[account(mut, address = points_config.reserve_vault)]
+    pub reserve_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for reserve vault authority
+    #[account(
+        seeds = [b"reserve_authority", points_config.key().as_ref()],
+        bump = points_config.reserve_authority_bump
+    )]
+    pub reserve_vault_authority: AccountInfo<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct UpdateContributorPoints<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"contributor", contributor.authority.as_ref()],
+        bump = contributor.bump,
+    )]
     pub contributor: Account<'info, Contributor>,
 
     #[account(mut)]
     pub points_config: Account<'info, PointsConfig>,
 
+    #[account(
+        seeds = [
+            b"moderator",
+            points_config.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = moderator.bump,
+        constraint = !moderator.is_suspended @ RewardError::ModeratorSuspended,
+        constraint = moderator.has_permission(PERMISSION_CLOSE_PERIOD) @ RewardError::Unauthorized,
+    )]
+    pub moderator: Account<'info, Moderator>,
+
+    // Pays for the current period's points account when points carry into it
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // The period that just closed; unpaid points may roll out of it
+    #[account(
+        mut,
+        seeds = [
+            b"distribution",
+            points_config.key().as_ref(),
+            &points_config.current_period.saturating_sub(1).to_le_bytes(),
+        ],
+        bump = distribution_period.bump
+    )]
+    pub distribution_period: Account<'info, DistributionPeriod>,
+
+    /// CHECK: Claim receipt PDA; only checked for existence
+    #[account(
+        seeds = [
+            b"claim_receipt",
+            distribution_period.key().as_ref(),
+            contributor.key().as_ref(),
+        ],
+        bump
+    )]
+    pub claim_receipt: AccountInfo<'info>,
+
+    // Absent when the contributor earned nothing in the closed period
+    #[account(
+        mut,
+        seeds = [
+            b"period_points",
+            points_config.key().as_ref(),
+            contributor.key().as_ref(),
+            &distribution_period.period.to_le_bytes(),
+        ],
+        bump = from_period_points.bump
+    )]
+    pub from_period_points: Option<Account<'info, ContributorPeriodPoints>>,
+
+    /// CHECK: ContributorPeriodPoints PDA for the current period, created by load_period_points
+    #[account(mut)]
+    pub to_period_points: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCarryForwardPolicy<'info> {
+    #[account(mut)]
+    pub points_config: Account<'info, PointsConfig>,
+
     #[account(
         constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
     )]
@@ -37,7 +163,7 @@ pub struct UpdateContributorPoints<'info> {
 }
 
 impl<'info> CalculateMonthlyPoints<'info> {
-    pub fn process(&mut self) -> Result<()> {
+    pub fn process(&mut self, bumps: &CalculateMonthlyPointsBumps) -> Result<()> {
         let clock = Clock::get()?;
         
         // Validate distribution period
@@ -53,17 +179,149 @@ impl<'info> CalculateMonthlyPoints<'info> {
             return Err(RewardError::InvalidPointsCalculation.into());
         }
 
-        // Check if we meet the minimum threshold
-        if total_points < self.points_config.monthly_threshold {
-            msg!("Below monthly threshold, applying reserve ratio");
-            
-            // Calculate reserve amount
-            let reserve_amount = self.points_config.calculate_reserve_amount(total_points)?;
-            
-            // Update points config with reserve
-            self.points_config.update_reserve(reserve_amount)?;
+        // Snapshot the pool for the closing period so every claimant is paid
+        // from the same numbers regardless of claim order. Tokens still owed
+        // to earlier periods are not available again.
+        let free_balance = self.points_config.free_reward_balance(self.reward_vault.amount);
+
+        let budget = if self.points_config.is_emission_scheduled() {
+            let budget = self.points_config.emission_budget(self.points_config.current_period)?;
+            require!(free_balance >= budget, RewardError::EmissionUnderfunded);
+
+            self.points_config.total_emitted = self.points_config.total_emitted
+                .checked_add(budget)
+                .ok_or(RewardError::InvalidPointsCalculation)?;
+            budget
+        } else {
+            free_balance
+        };
+
+        let mut pool = self.points_config.calculate_distribution_amount(
+            total_points,
+            budget,
+        )?;
+        let reserve_amount = budget
+            .checked_sub(pool)
+            .ok_or(RewardError::ReserveCalculationError)?;
+
+        self.points_config.commit_rewards(pool)?;
+
+        // Below threshold the share withheld by the reserve ratio leaves the
+        // reward vault so it cannot be paid out by a later period
+        let period = self.points_config.current_period;
+        let mut reserve_transferred = 0;
+
+        if reserve_amount > 0 {
+            msg!("Below monthly threshold, moving withheld share to reserve");
+
+            let points_config_pubkey = self.points_config.key();
+            let seeds = &[
+                b"vault_authority".as_ref(),
+                points_config_pubkey.as_ref(),
+                &[self.points_config.vault_authority_bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            reserve_transferred = transfer_checked_net(
+                &self.token_program,
+                &self.reward_vault,
+                &mut self.reserve_vault,
+                &self.reward_mint,
+                &self.reward_vault_authority,
+                reserve_amount,
+                signer_seeds,
+            )?;
+
+            let (balance_before, balance_after) =
+                self.reserve_state.record_inflow(reserve_transferred, period)?;
+
+            emit!(PeriodReserveSplit {
+                period,
+                reserve_amount,
+                amount: reserve_transferred,
+                fee: reserve_amount - reserve_transferred,
+                balance_before,
+                balance_after,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        // A period that meets the threshold draws part of the reserve back
+        // into its pool
+        let release = self.points_config
+            .reserve_release_amount(total_points, self.reserve_state.balance)?
+            .min(self.reserve_vault.amount);
+        let mut reserve_released = 0;
+
+        if release > 0 {
+            let points_config_pubkey = self.points_config.key();
+            let seeds = &[
+                b"reserve_authority".as_ref(),
+                points_config_pubkey.as_ref(),
+                &[self.points_config.reserve_authority_bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            reserve_released = transfer_checked_net(
+                &self.token_program,
+                &self.reserve_vault,
+                &mut self.reward_vault,
+                &self.reward_mint,
+                &self.reserve_vault_authority,
+                release,
+                signer_seeds,
+            )?;
+
+            let (balance_before, balance_after) = self.reserve_state.record_outflow(release)?;
+
+            pool = pool
+                .checked_add(reserve_released)
+                .ok_or(RewardError::InvalidPointsCalculation)?;
+            self.points_config.commit_rewards(reserve_released)?;
+
+            emit!(PeriodReserveReleased {
+                period,
+                amount: reserve_released,
+                fee: release - reserve_released,
+                balance_before,
+                balance_after,
+                timestamp: clock.unix_timestamp,
+            });
         }
 
+        self.distribution_period.period = period;
+        self.distribution_period.total_points = total_points;
+        self.distribution_period.total_tokens = pool;
+        self.distribution_period.vault_balance = free_balance;
+        self.distribution_period.reserve_amount = reserve_amount;
+        self.distribution_period.reserve_transferred = reserve_transferred;
+        self.distribution_period.reserve_released = reserve_released;
+        self.distribution_period.tokens_distributed = 0;
+        self.distribution_period.points_claimed = 0;
+        self.distribution_period.points_carried = 0;
+        self.distribution_period.dust_swept = 0;
+        self.distribution_period.merkle_root = [0u8; 32];
+        self.distribution_period.merkle_total_amount = 0;
+        self.distribution_period.claim_deadline = if self.points_config.claim_window > 0 {
+            clock.unix_timestamp
+                .checked_add(self.points_config.claim_window)
+                .ok_or(RewardError::InvalidPointsCalculation)?
+        } else {
+            0
+        };
+        self.distribution_period.unclaimed_swept = 0;
+        self.distribution_period.max_payout = if self.points_config.max_share_bps > 0 {
+            ((pool as u128) * (self.points_config.max_share_bps as u128) / 10000) as u64
+        } else {
+            0
+        };
+        self.distribution_period.redistribute_excess = self.points_config.redistribute_excess;
+        self.distribution_period.excess_to_reserve = 0;
+        self.distribution_period.is_completed = false;
+        self.distribution_period.start_time = self.points_config.last_calculation_time;
+        self.distribution_period.end_time = clock.unix_timestamp;
+        self.distribution_period.bump = bumps.distribution_period;
+
         // Update period and reset counters
         self.points_config.current_period = self.points_config.current_period
             .checked_add(1)
@@ -76,6 +334,10 @@ impl<'info> CalculateMonthlyPoints<'info> {
         emit!(MonthlyPointsCalculated {
             period: self.points_config.current_period,
             total_points,
+            budget,
+            pool,
+            reserve_amount,
+            reserve_released,
             timestamp: clock.unix_timestamp,
             meets_threshold: total_points >= self.points_config.monthly_threshold,
         });
@@ -86,8 +348,11 @@ impl<'info> CalculateMonthlyPoints<'info> {
 
 impl<'info> UpdateContributorPoints<'info> {
     pub fn process(&mut self) -> Result<()> {
-        // Reset monthly points for new period
-        self.contributor.current_month_points = 0;
+        let carried_points = if self.should_carry_forward()? {
+            self.carry_forward()?
+        } else {
+            0
+        };
         
         // Keep track of total points (historical)
         // No need to reset total_points as it's cumulative
@@ -95,24 +360,164 @@ impl<'info> UpdateContributorPoints<'info> {
         emit!(ContributorPointsUpdated {
             contributor: self.contributor.key(),
             total_points: self.contributor.total_points,
+            carried_points,
             period: self.points_config.current_period,
         });
 
         Ok(())
     }
+
+    // Points carry forward when the closed month missed the threshold or the
+    // contributor's share falls below the configured minimum payout
+    fn should_carry_forward(&self) -> Result<bool> {
+        let period = &self.distribution_period;
+        let points = self.from_period_points
+            .as_ref()
+            .map_or(0, |period_points| period_points.claimable());
+
+        if !self.points_config.carry_forward_enabled
+            || points == 0
+            || period.has_merkle_root()
+            || period.requires_merkle_distribution()
+            || period.is_completed
+            || !self.claim_receipt.data_is_empty()
+        {
+            return Ok(false);
+        }
+
+        let below_threshold = period.total_points < self.points_config.monthly_threshold;
+        let below_min_payout =
+            period.payout_for_points(points)? < self.points_config.carry_forward_min_payout;
+
+        Ok(below_threshold || below_min_payout)
+    }
+
+    // Returns the points that arrived in the current period after decay
+    fn carry_forward(&mut self) -> Result<u64> {
+        let from_period = self.distribution_period.period;
+        let to_period = self.points_config.current_period;
+        let points = self.from_period_points
+            .as_mut()
+            .ok_or(RewardError::NothingToClaim)?
+            .carry_out()?;
+        let carried = self.points_config.apply_carry_forward_decay(points)?;
+
+        let to_info = self.to_period_points.to_account_info();
+        let mut to_period_points = load_period_points(
+            &self.authority.to_account_info(),
+            &to_info,
+            &self.system_program.to_account_info(),
+            self.points_config.key(),
+            self.contributor.key(),
+            to_period,
+        )?;
+        to_period_points.carry_in(from_period, carried)?;
+        write_account(&to_info, &to_period_points)?;
+
+        self.contributor.carry_points(carried, from_period, to_period)?;
+
+        // The closed period's share of these points is left for the reserve
+        self.distribution_period.points_carried = self.distribution_period.points_carried
+            .checked_add(points)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        self.points_config.update_period_points(carried)?;
+
+        emit!(ContributorPointsCarried {
+            contributor: self.contributor.key(),
+            from_period,
+            to_period,
+            points,
+            carried,
+            decayed: points - carried,
+        });
+
+        Ok(carried)
+    }
+}
+
+impl<'info> UpdateCarryForwardPolicy<'info> {
+    pub fn process(
+        &mut self,
+        enabled: bool,
+        min_payout: u64,
+        decay_bps: u16,
+    ) -> Result<()> {
+        self.points_config.carry_forward_enabled = enabled;
+        self.points_config.carry_forward_min_payout = min_payout;
+        self.points_config.carry_forward_decay_bps = decay_bps;
+
+        emit!(CarryForwardPolicyUpdated {
+            points_config: self.points_config.key(),
+            enabled,
+            min_payout,
+            decay_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
 }
 
 #[event]
 pub struct MonthlyPointsCalculated {
     pub period: u16,
     pub total_points: u64,
+    pub budget: u64,
+    pub pool: u64,
+    pub reserve_amount: u64,
+    pub reserve_released: u64,
     pub timestamp: i64,
     pub meets_threshold: bool,
 }
 
+#[event]
+pub struct PeriodReserveReleased {
+    pub period: u16,
+    // Net amount added to the period pool after transfer fees
+    pub amount: u64,
+    pub fee: u64,
+    pub balance_before: u64,
+    pub balance_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PeriodReserveSplit {
+    pub period: u16,
+    // Withheld share taken from the reward vault
+    pub reserve_amount: u64,
+    // Net amount received by the reserve after transfer fees
+    pub amount: u64,
+    pub fee: u64,
+    pub balance_before: u64,
+    pub balance_after: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct ContributorPointsUpdated {
     pub contributor: Pubkey,
     pub total_points: u64,
+    pub carried_points: u64,
     pub period: u16,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct ContributorPointsCarried {
+    pub contributor: Pubkey,
+    pub from_period: u16,
+    pub to_period: u16,
+    pub points: u64,
+    pub carried: u64,
+    pub decayed: u64,
+}
+
+#[event]
+pub struct CarryForwardPolicyUpdated {
+    pub points_config: Pubkey,
+    pub enabled: bool,
+    pub min_payout: u64,
+    pub decay_bps: u16,
+    pub timestamp: i64,
+}
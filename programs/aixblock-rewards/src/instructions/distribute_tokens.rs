@@ -1,108 +1,172 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::{
     contributor::Contributor,
+    distribution::{ClaimReceipt, DistributionPeriod},
+    period_points::ContributorPeriodPoints,
     points::PointsConfig,
+    reserve::ReserveState,
+    vesting::VestingEscrow,
 };
 use crate::errors::RewardError;
+use crate::utils::transfer_checked_net;
 
 #[derive(Accounts)]
-#[instruction(vault_authority_bump: u8)]
+#[instruction(period: u16)]
 pub struct DistributeTokens<'info> {
     #[account(mut)]
     pub points_config: Account<'info, PointsConfig>,
 
-    #[account(mut)]
-    pub contributor: Account<'info, Contributor>,
+    #[account(
+        mut,
+        seeds = [
+            b"distribution",
+            points_config.key().as_ref(),
+            &period.to_le_bytes(),
+        ],
+        bump = distribution_period.bump,
+        constraint = !distribution_period.has_merkle_root() @ RewardError::MerkleDistributionActive,
+        constraint = !distribution_period.requires_merkle_distribution() @ RewardError::MerkleDistributionRequired,
+    )]
+    pub distribution_period: Account<'info, DistributionPeriod>,
+
+    // Exists once the contributor has claimed, so a second claim fails
+    #[account(
+        init,
+        payer = payer,
+        space = ClaimReceipt::SPACE,
+        seeds = [
+            b"claim_receipt",
+            distribution_period.key().as_ref(),
+            contributor.key().as_ref(),
+        ],
+        bump
+    )]
+    pub claim_receipt: Account<'info, ClaimReceipt>,
 
     #[account(
         mut,
-        constraint = reward_vault.owner == reward_vault_authority.key(),
+        seeds = [b"contributor", contributor.authority.as_ref()],
+        bump = contributor.bump,
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    pub contributor: Account<'info, Contributor>,
 
-    #[account(mut)]
-    pub contributor_token_account: Account<'info, TokenAccount>,
+    // Points the contributor earned under this config in the period
+    #[account(
+        seeds = [
+            b"period_points",
+            points_config.key().as_ref(),
+            contributor.key().as_ref(),
+            &period.to_le_bytes(),
+        ],
+        bump = period_points.bump
+    )]
+    pub period_points: Account<'info, ContributorPeriodPoints>,
+
+    #[account(address = points_config.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = points_config.reward_vault)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = contributor_token_account.mint == reward_mint.key(),
+        constraint = contributor.is_payout_destination(
+            &contributor_token_account.key(),
+            &reward_mint.key(),
+            &token_program.key(),
+        ) @ RewardError::InvalidPayoutDestination,
+    )]
+    pub contributor_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA for reward vault authority
+    #[account(
+        seeds = [b"vault_authority", points_config.key().as_ref()],
+        bump = points_config.vault_authority_bump
+    )]
     pub reward_vault_authority: AccountInfo<'info>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    // Receives the rounding dust once the period is fully claimed
+    #[account(mut, address = points_config.reserve_vault)]
+    pub reserve_vault: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    /// CHECK: PDA for reserve vault authority
+    #[account(
+        seeds = [b"reserve_authority", points_config.key().as_ref()],
+        bump = points_config.reserve_authority_bump
+    )]
+    pub reserve_vault_authority: AccountInfo<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+
+    // Holds the locked share of the payout; only needed while the config's
+    // vesting policy locks part of it
     #[account(
         init,
-        payer = authority,
-        space = DistributionPeriod::SPACE,
+        payer = payer,
+        space = VestingEscrow::SPACE,
         seeds = [
-            b"distribution",
+            b"vesting_escrow",
             points_config.key().as_ref(),
-            &points_config.current_period.to_le_bytes(),
+            contributor.key().as_ref(),
+            &period.to_le_bytes(),
         ],
         bump
     )]
-    pub distribution_period: Account<'info, DistributionPeriod>,
-}
+    pub vesting_escrow: Option<Account<'info, VestingEscrow>>,
 
-#[account]
-pub struct DistributionPeriod {
-    pub period: u16,
-    pub total_tokens: u64,
-    pub tokens_distributed: u64,
-    pub total_points: u64,
-    pub is_completed: bool,
-    pub start_time: i64,
-    pub end_time: i64,
-    pub bump: u8,
+    #[account(mut, address = points_config.vesting_vault)]
+    pub vesting_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // Any cranker may trigger the claim and pay the rent; the destination is
+    // fixed by the contributor record
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
 }
 
-impl DistributionPeriod {
-    pub const SPACE: usize = 8 + // discriminator
-        2 +     // period
-        8 +     // total_tokens
-        8 +     // tokens_distributed
-        8 +     // total_points
-        1 +     // is_completed
-        8 +     // start_time
-        8 +     // end_time
-        1;      // bump
+#[derive(Accounts)]
+pub struct UpdatePayoutCap<'info> {
+    #[account(mut)]
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
 }
 
 impl<'info> DistributeTokens<'info> {
     pub fn process(
         &mut self,
-        vault_authority_bump: u8,
+        bumps: &DistributeTokensBumps,
     ) -> Result<()> {
         let clock = Clock::get()?;
+        let period = self.distribution_period.period;
+        self.distribution_period.validate_claimable(clock.unix_timestamp)?;
 
-        // Initialize distribution period
-        self.distribution_period.period = self.points_config.current_period;
-        self.distribution_period.start_time = clock.unix_timestamp;
-        
         // Store points config pubkey for seeds
         let points_config_pubkey = self.points_config.key();
-        
-        // Get bump from seeds
-        let (_, bump) = Pubkey::find_program_address(
-            &[
-                b"distribution".as_ref(),
-                points_config_pubkey.as_ref(),
-                &self.points_config.current_period.to_le_bytes(),
-            ],
-            &crate::ID,
-        );
-        self.distribution_period.bump = bump;
-
-        // Verify distribution period is active
-        if self.contributor.last_claim_time >= clock.unix_timestamp {
-            return Err(RewardError::DistributionAlreadyProcessed.into());
-        }
 
-        // Calculate tokens to distribute
-        let tokens_to_distribute = self.calculate_tokens_for_contributor()?;
+        let contributor_points = self.period_points.claimable();
+        require!(contributor_points > 0, RewardError::NothingToClaim);
+
+        // Calculate tokens to distribute from the pool snapshot taken at close,
+        // limited to the per-contributor share cap and to what the pool
+        // still holds
+        let (tokens_to_distribute, excess) =
+            self.distribution_period.apply_payout_cap(contributor_points)?;
+        let remaining = self.distribution_period.unsettled_tokens();
+        let tokens_to_distribute = tokens_to_distribute.min(remaining);
+        let excess = excess.min(remaining - tokens_to_distribute);
 
         if tokens_to_distribute == 0 {
             return Err(RewardError::InsufficientBalance.into());
@@ -112,85 +176,245 @@ impl<'info> DistributeTokens<'info> {
         let seeds = &[
             b"vault_authority".as_ref(),
             points_config_pubkey.as_ref(),
-            &[vault_authority_bump],
+            &[self.points_config.vault_authority_bump],
         ];
         let signer_seeds = &[&seeds[..]];
 
-        let transfer_ix = Transfer {
-            from: self.reward_vault.to_account_info(),
-            to: self.contributor_token_account.to_account_info(),
-            authority: self.reward_vault_authority.to_account_info(),
+        // Release the unlocked share now and escrow the rest. Amounts below
+        // are net of any Token-2022 transfer fee.
+        let (immediate, locked) = self.points_config.split_vested_payout(tokens_to_distribute)?;
+
+        let released = if immediate > 0 {
+            transfer_checked_net(
+                &self.token_program,
+                &self.reward_vault,
+                &mut self.contributor_token_account,
+                &self.reward_mint,
+                &self.reward_vault_authority,
+                immediate,
+                signer_seeds,
+            )?
+        } else {
+            0
         };
 
-        token::transfer(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                transfer_ix,
+        let locked = if locked > 0 {
+            require!(self.vesting_escrow.is_some(), RewardError::VestingAccountsRequired);
+            let vesting_vault = self.vesting_vault
+                .as_mut()
+                .ok_or(RewardError::VestingAccountsRequired)?;
+
+            transfer_checked_net(
+                &self.token_program,
+                &self.reward_vault,
+                vesting_vault,
+                &self.reward_mint,
+                &self.reward_vault_authority,
+                locked,
                 signer_seeds,
-            ),
-            tokens_to_distribute,
-        )?;
+            )?
+        } else {
+            0
+        };
+
+        let received = released
+            .checked_add(locked)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        if let Some(vesting_escrow) = self.vesting_escrow.as_mut() {
+            vesting_escrow.open(
+                &self.points_config,
+                self.contributor.key(),
+                period,
+                locked,
+                clock.unix_timestamp,
+                bumps.vesting_escrow.ok_or(RewardError::VestingAccountsRequired)?,
+            )?;
+
+            emit!(TokensVested {
+                contributor: self.contributor.key(),
+                period,
+                released,
+                locked,
+                cliff_time: vesting_escrow.cliff_time,
+                end_time: vesting_escrow.end_time,
+            });
+        }
 
         // Update contributor state
         self.contributor.tokens_claimed = self.contributor.tokens_claimed
-            .checked_add(tokens_to_distribute)
+            .checked_add(received)
             .ok_or(RewardError::InvalidPointsCalculation)?;
         
         self.contributor.last_claim_time = clock.unix_timestamp;
-        self.contributor.current_month_points = 0; // Reset monthly points
+
+        self.points_config.release_committed_rewards(tokens_to_distribute);
+
+        // The capped excess goes to the reserve now
+        if excess > 0 {
+            let received = transfer_checked_net(
+                &self.token_program,
+                &self.reward_vault,
+                &mut self.reserve_vault,
+                &self.reward_mint,
+                &self.reward_vault_authority,
+                excess,
+                signer_seeds,
+            )?;
+
+            self.points_config.release_committed_rewards(excess);
+            self.distribution_period.excess_to_reserve = self.distribution_period.excess_to_reserve
+                .checked_add(excess)
+                .ok_or(RewardError::InvalidPointsCalculation)?;
+
+            let (balance_before, balance_after) = self.reserve_state.record_inflow(received, period)?;
+
+            emit!(PayoutExcessReserved {
+                contributor: self.contributor.key(),
+                period,
+                amount: received,
+                balance_before,
+                balance_after,
+                timestamp: clock.unix_timestamp,
+            });
+        }
 
         // Update distribution period state
         self.distribution_period.tokens_distributed = self.distribution_period.tokens_distributed
             .checked_add(tokens_to_distribute)
             .ok_or(RewardError::InvalidPointsCalculation)?;
 
-        // Set total tokens for the period if not set
-        if self.distribution_period.total_tokens == 0 {
-            self.distribution_period.total_tokens = self.reward_vault.amount;
+        self.distribution_period.points_claimed = self.distribution_period.points_claimed
+            .checked_add(contributor_points)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        // Once every point is paid, the rounding remainder goes to the reserve
+        if self.distribution_period.is_fully_settled() {
+            let dust = self.distribution_period.unsettled_tokens();
+
+            if dust > 0 {
+                let received = transfer_checked_net(
+                    &self.token_program,
+                    &self.reward_vault,
+                    &mut self.reserve_vault,
+                    &self.reward_mint,
+                    &self.reward_vault_authority,
+                    dust,
+                    signer_seeds,
+                )?;
+
+                let (balance_before, balance_after) =
+                    self.reserve_state.record_inflow(received, period)?;
+
+                emit!(PeriodDustSwept {
+                    period,
+                    amount: received,
+                    balance_before,
+                    balance_after,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+
+            self.points_config.release_committed_rewards(dust);
+            self.distribution_period.dust_swept = dust;
+            self.distribution_period.is_completed = true;
         }
 
-        // Update total points for the period
-        self.distribution_period.total_points = self.points_config.period_total_points;
+        // Record the claim
+        self.claim_receipt.distribution_period = self.distribution_period.key();
+        self.claim_receipt.contributor = self.contributor.key();
+        self.claim_receipt.points = contributor_points;
+        self.claim_receipt.amount = received;
+        self.claim_receipt.claimed_at = clock.unix_timestamp;
+        self.claim_receipt.bump = bumps.claim_receipt;
 
         // Emit event
         emit!(TokensDistributed {
             contributor: self.contributor.key(),
-            amount: tokens_to_distribute,
-            period: self.points_config.current_period,
+            recipient: self.contributor_token_account.key(),
+            mint: self.reward_mint.key(),
+            amount: received,
+            fee: tokens_to_distribute - received,
+            points: contributor_points,
+            total_points: self.distribution_period.total_points,
+            pool: self.distribution_period.total_tokens,
+            cap: self.distribution_period.max_payout,
+            excess,
+            period,
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
+}
 
-    fn calculate_tokens_for_contributor(&self) -> Result<u64> {
-        let contributor_points = self.contributor.current_month_points;
-        let total_period_points = self.points_config.period_total_points;
-        
-        if total_period_points == 0 || contributor_points == 0 {
-            return Ok(0);
-        }
-
-        let monthly_pool = self.points_config.calculate_distribution_amount(
-            total_period_points,
-            self.reward_vault.amount,
-        )?;
+impl<'info> UpdatePayoutCap<'info> {
+    pub fn process(&mut self, max_share_bps: u16, redistribute_excess: bool) -> Result<()> {
+        self.points_config.max_share_bps = max_share_bps;
+        self.points_config.redistribute_excess = redistribute_excess;
 
-        // Calculate proportional share
-        let tokens_to_distribute = (monthly_pool as u128)
-            .checked_mul(contributor_points as u128)
-            .ok_or(RewardError::InvalidPointsCalculation)?
-            .checked_div(total_period_points as u128)
-            .ok_or(RewardError::InvalidPointsCalculation)?;
+        emit!(PayoutCapUpdated {
+            points_config: self.points_config.key(),
+            max_share_bps,
+            redistribute_excess,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        Ok(tokens_to_distribute as u64)
+        Ok(())
     }
 }
 
 #[event]
 pub struct TokensDistributed {
     pub contributor: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    // Net amount received after transfer fees
     pub amount: u64,
+    pub fee: u64,
+    pub points: u64,
+    pub total_points: u64,
+    pub pool: u64,
+    // Per-contributor cap and the amount it held back from this payout
+    pub cap: u64,
+    pub excess: u64,
     pub period: u16,
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct TokensVested {
+    pub contributor: Pubkey,
+    pub period: u16,
+    pub released: u64,
+    pub locked: u64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct PeriodDustSwept {
+    pub period: u16,
+    pub amount: u64,
+    pub balance_before: u64,
+    pub balance_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayoutCapUpdated {
+    pub points_config: Pubkey,
+    pub max_share_bps: u16,
+    pub redistribute_excess: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayoutExcessReserved {
+    pub contributor: Pubkey,
+    pub period: u16,
+    pub amount: u64,
+    pub balance_before: u64,
+    pub balance_after: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::points::PointsConfig;
+use crate::errors::RewardError;
+
+#[derive(Accounts)]
+pub struct UpdateEmissionSchedule<'info> {
+    #[account(mut)]
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> UpdateEmissionSchedule<'info> {
+    pub fn process(
+        &mut self,
+        per_period: u64,
+        decay_bps: u16,
+        decay_interval: u16,
+        total_cap: u64,
+    ) -> Result<()> {
+        // A new emission curve restarts decay from the current period, so the
+        // authority can re-base the schedule by changing it; changing only
+        // the cap keeps the decay position. Emissions so far still count
+        // toward the cap.
+        let curve_changed = self.points_config.emission_per_period != per_period
+            || self.points_config.emission_decay_bps != decay_bps
+            || self.points_config.emission_decay_interval != decay_interval;
+
+        self.points_config.emission_per_period = per_period;
+        self.points_config.emission_decay_bps = decay_bps;
+        self.points_config.emission_decay_interval = decay_interval;
+        self.points_config.emission_cap = total_cap;
+
+        if curve_changed {
+            self.points_config.emission_start_period = self.points_config.current_period;
+        }
+
+        emit!(EmissionScheduleUpdated {
+            points_config: self.points_config.key(),
+            per_period,
+            decay_bps,
+            decay_interval,
+            total_cap,
+            start_period: self.points_config.emission_start_period,
+            total_emitted: self.points_config.total_emitted,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct EmissionScheduleUpdated {
+    pub points_config: Pubkey,
+    pub per_period: u64,
+    pub decay_bps: u16,
+    pub decay_interval: u16,
+    pub total_cap: u64,
+    pub start_period: u16,
+    pub total_emitted: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,266 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::points::PointsConfig;
+use crate::errors::RewardError;
+
+// Creates the program vaults once per config. Later instructions check vault
+// addresses and authority bumps against the values stored here.
+#[derive(Accounts)]
+pub struct InitializeVaults<'info> {
+    #[account(
+        mut,
+        constraint = !points_config.has_vaults() @ RewardError::VaultsAlreadyInitialized,
+    )]
+    pub points_config: Box<Account<'info, PointsConfig>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: PDA for reward vault authority
+    #[account(
+        seeds = [b"vault_authority", points_config.key().as_ref()],
+        bump
+    )]
+    pub reward_vault_authority: AccountInfo<'info>,
+
+    /// CHECK: PDA for reserve vault authority
+    #[account(
+        seeds = [b"reserve_authority", points_config.key().as_ref()],
+        bump
+    )]
+    pub reserve_vault_authority: AccountInfo<'info>,
+
+    /// CHECK: PDA for distribution vault authority
+    #[account(
+        seeds = [b"distribution_authority", points_config.key().as_ref()],
+        bump
+    )]
+    pub distribution_vault_authority: AccountInfo<'info>,
+
+    /// CHECK: PDA for vesting vault authority
+    #[account(
+        seeds = [b"vesting_authority", points_config.key().as_ref()],
+        bump
+    )]
+    pub vesting_vault_authority: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"reward_vault", points_config.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = reward_vault_authority,
+        token::token_program = token_program,
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"reserve_vault", points_config.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = reserve_vault_authority,
+        token::token_program = token_program,
+    )]
+    pub reserve_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"distribution_vault", points_config.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = distribution_vault_authority,
+        token::token_program = token_program,
+    )]
+    pub distribution_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"vesting_vault", points_config.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = vesting_vault_authority,
+        token::token_program = token_program,
+    )]
+    pub vesting_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Configs created before InitializeVaults hold their funds in token accounts
+// owned by the same authority PDAs; this records those accounts as the
+// program vaults instead of creating empty ones, and creates the vesting vault
+#[derive(Accounts)]
+pub struct AdoptVaults<'info> {
+    #[account(
+        mut,
+        constraint = !points_config.has_vaults() @ RewardError::VaultsAlreadyInitialized,
+    )]
+    pub points_config: Box<Account<'info, PointsConfig>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: PDA for reward vault authority
+    #[account(
+        seeds = [b"vault_authority", points_config.key().as_ref()],
+        bump
+    )]
+    pub reward_vault_authority: AccountInfo<'info>,
+
+    /// CHECK: PDA for reserve vault authority
+    #[account(
+        seeds = [b"reserve_authority", points_config.key().as_ref()],
+        bump
+    )]
+    pub reserve_vault_authority: AccountInfo<'info>,
+
+    /// CHECK: PDA for distribution vault authority
+    #[account(
+        seeds = [b"distribution_authority", points_config.key().as_ref()],
+        bump
+    )]
+    pub distribution_vault_authority: AccountInfo<'info>,
+
+    /// CHECK: PDA for vesting vault authority
+    #[account(
+        seeds = [b"vesting_authority", points_config.key().as_ref()],
+        bump
+    )]
+    pub vesting_vault_authority: AccountInfo<'info>,
+
+    #[account(
+        constraint = reward_vault.owner == reward_vault_authority.key() @ RewardError::InvalidAccountAddress,
+        constraint = reward_vault.mint == reward_mint.key() @ RewardError::InvalidAccountAddress,
+        constraint = is_adoptable(&reward_vault) @ RewardError::VaultNotAdoptable,
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = reserve_vault.owner == reserve_vault_authority.key() @ RewardError::InvalidAccountAddress,
+        constraint = reserve_vault.mint == reward_mint.key() @ RewardError::InvalidAccountAddress,
+        constraint = is_adoptable(&reserve_vault) @ RewardError::VaultNotAdoptable,
+    )]
+    pub reserve_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = distribution_vault.owner == distribution_vault_authority.key() @ RewardError::InvalidAccountAddress,
+        constraint = distribution_vault.mint == reward_mint.key() @ RewardError::InvalidAccountAddress,
+        constraint = is_adoptable(&distribution_vault) @ RewardError::VaultNotAdoptable,
+    )]
+    pub distribution_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"vesting_vault", points_config.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = vesting_vault_authority,
+        token::token_program = token_program,
+    )]
+    pub vesting_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Nobody but the authority PDA may move or close an adopted vault
+fn is_adoptable(vault: &TokenAccount) -> bool {
+    vault.delegate.is_none() && vault.close_authority.is_none()
+}
+
+impl<'info> InitializeVaults<'info> {
+    pub fn process(&mut self, bumps: &InitializeVaultsBumps) -> Result<()> {
+        self.points_config.reward_mint = self.reward_mint.key();
+        self.points_config.reward_vault = self.reward_vault.key();
+        self.points_config.reserve_vault = self.reserve_vault.key();
+        self.points_config.distribution_vault = self.distribution_vault.key();
+        self.points_config.vesting_vault = self.vesting_vault.key();
+        self.points_config.vault_authority_bump = bumps.reward_vault_authority;
+        self.points_config.reserve_authority_bump = bumps.reserve_vault_authority;
+        self.points_config.distribution_authority_bump = bumps.distribution_vault_authority;
+        self.points_config.vesting_authority_bump = bumps.vesting_vault_authority;
+
+        emit!(VaultsInitialized {
+            points_config: self.points_config.key(),
+            reward_mint: self.reward_mint.key(),
+            reward_vault: self.reward_vault.key(),
+            reserve_vault: self.reserve_vault.key(),
+            distribution_vault: self.distribution_vault.key(),
+            vesting_vault: self.vesting_vault.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> AdoptVaults<'info> {
+    pub fn process(&mut self, bumps: &AdoptVaultsBumps) -> Result<()> {
+        self.points_config.reward_mint = self.reward_mint.key();
+        self.points_config.reward_vault = self.reward_vault.key();
+        self.points_config.reserve_vault = self.reserve_vault.key();
+        self.points_config.distribution_vault = self.distribution_vault.key();
+        self.points_config.vesting_vault = self.vesting_vault.key();
+        self.points_config.vault_authority_bump = bumps.reward_vault_authority;
+        self.points_config.reserve_authority_bump = bumps.reserve_vault_authority;
+        self.points_config.distribution_authority_bump = bumps.distribution_vault_authority;
+        self.points_config.vesting_authority_bump = bumps.vesting_vault_authority;
+
+        emit!(VaultsAdopted {
+            points_config: self.points_config.key(),
+            reward_mint: self.reward_mint.key(),
+            reward_vault: self.reward_vault.key(),
+            reserve_vault: self.reserve_vault.key(),
+            distribution_vault: self.distribution_vault.key(),
+            vesting_vault: self.vesting_vault.key(),
+            reward_vault_balance: self.reward_vault.amount,
+            reserve_vault_balance: self.reserve_vault.amount,
+            distribution_vault_balance: self.distribution_vault.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct VaultsInitialized {
+    pub points_config: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_vault: Pubkey,
+    pub reserve_vault: Pubkey,
+    pub distribution_vault: Pubkey,
+    pub vesting_vault: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultsAdopted {
+    pub points_config: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_vault: Pubkey,
+    pub reserve_vault: Pubkey,
+    pub distribution_vault: Pubkey,
+    pub vesting_vault: Pubkey,
+    pub reward_vault_balance: u64,
+    pub reserve_vault_balance: u64,
+    pub distribution_vault_balance: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::contributor::Contributor;
+
+#[derive(Accounts)]
+pub struct SetBeneficiary<'info> {
+    #[account(
+        mut,
+        seeds = [b"contributor", authority.key().as_ref()],
+        bump = contributor.bump,
+    )]
+    pub contributor: Account<'info, Contributor>,
+
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetBeneficiary<'info> {
+    // Pubkey::default() clears the beneficiary, leaving only the authority's ATA
+    pub fn process(&mut self, beneficiary: Pubkey) -> Result<()> {
+        let old_beneficiary = self.contributor.beneficiary;
+        self.contributor.beneficiary = beneficiary;
+
+        emit!(BeneficiaryUpdated {
+            contributor: self.contributor.key(),
+            old_beneficiary,
+            new_beneficiary: beneficiary,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct BeneficiaryUpdated {
+    pub contributor: Pubkey,
+    pub old_beneficiary: Pubkey,
+    pub new_beneficiary: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,264 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    contribution::{ContributionType, LEGACY_CONTRIBUTION_TYPE_COUNT},
+    contribution_type::ContributionTypeDef,
+    points::{PointsConfig, DEFAULT_BASE_POINTS},
+};
+use crate::errors::RewardError;
+use crate::utils::{create_pda_account, write_account};
+
+#[derive(Accounts)]
+#[instruction(type_id: u8)]
+pub struct RegisterContributionType<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ContributionTypeDef::SPACE,
+        seeds = [
+            b"contribution_type",
+            points_config.key().as_ref(),
+            &[type_id],
+        ],
+        bump
+    )]
+    pub contribution_type: Account<'info, ContributionTypeDef>,
+
+    #[account(
+        mut,
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateContributionType<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"contribution_type",
+            points_config.key().as_ref(),
+            &[contribution_type.type_id],
+        ],
+        bump = contribution_type.bump
+    )]
+    pub contribution_type: Account<'info, ContributionTypeDef>,
+
+    #[account(
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+// Remaining accounts: the ContributionTypeDef PDAs for every legacy
+// ContributionType, in enum order
+#[derive(Accounts)]
+pub struct UpdateBasePoints<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> RegisterContributionType<'info> {
+    pub fn process(
+        &mut self,
+        type_id: u8,
+        name: String,
+        base_points: u64,
+        requires_verification: bool,
+        bump: u8,
+    ) -> Result<()> {
+        // Legacy ids are pre-registered when the config is created
+        require!(
+            type_id as usize >= LEGACY_CONTRIBUTION_TYPE_COUNT,
+            RewardError::InvalidContributionType
+        );
+
+        self.contribution_type.set_inner(ContributionTypeDef {
+            points_config: self.points_config.key(),
+            type_id,
+            name: name.clone(),
+            base_points,
+            is_enabled: true,
+            requires_verification,
+            bump,
+        });
+
+        emit!(ContributionTypeRegistered {
+            points_config: self.points_config.key(),
+            type_id,
+            name,
+            base_points,
+            requires_verification,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> UpdateContributionType<'info> {
+    pub fn process(
+        &mut self,
+        new_base_points: Option<u64>,
+        is_enabled: Option<bool>,
+        requires_verification: Option<bool>,
+    ) -> Result<()> {
+        let old_base_points = self.contribution_type.base_points;
+
+        if let Some(base_points) = new_base_points {
+            self.contribution_type.base_points = base_points;
+        }
+
+        if let Some(enabled) = is_enabled {
+            self.contribution_type.is_enabled = enabled;
+        }
+
+        if let Some(requires) = requires_verification {
+            self.contribution_type.requires_verification = requires;
+        }
+
+        emit!(ContributionTypeUpdated {
+            points_config: self.points_config.key(),
+            type_id: self.contribution_type.type_id,
+            old_base_points,
+            new_base_points: self.contribution_type.base_points,
+            is_enabled: self.contribution_type.is_enabled,
+            requires_verification: self.contribution_type.requires_verification,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> UpdateBasePoints<'info> {
+    pub fn process(
+        &mut self,
+        new_base_points: [u64; LEGACY_CONTRIBUTION_TYPE_COUNT],
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            remaining_accounts.len() == LEGACY_CONTRIBUTION_TYPE_COUNT,
+            RewardError::InvalidContributionType
+        );
+
+        let points_config_key = self.points_config.key();
+        let mut old_base_points = [0u64; LEGACY_CONTRIBUTION_TYPE_COUNT];
+
+        for (legacy_type, account) in ContributionType::ALL.iter().zip(remaining_accounts) {
+            let type_id = legacy_type.id();
+            let mut type_def = Account::<ContributionTypeDef>::try_from(account)?;
+            require!(
+                type_def.points_config == points_config_key && type_def.type_id == type_id,
+                RewardError::InvalidAccountAddress
+            );
+
+            old_base_points[type_id as usize] = type_def.base_points;
+            type_def.base_points = new_base_points[type_id as usize];
+            type_def.exit(&crate::ID)?;
+        }
+
+        emit!(BasePointsUpdated {
+            points_config: points_config_key,
+            old_base_points,
+            new_base_points,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+// Creates a ContributionTypeDef for every legacy ContributionType from
+// `type_accounts`, in enum order, with the legacy base points
+pub fn register_legacy_contribution_types<'info>(
+    points_config: Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    type_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    require!(
+        type_accounts.len() == LEGACY_CONTRIBUTION_TYPE_COUNT,
+        RewardError::InvalidContributionType
+    );
+
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    for (legacy_type, account) in ContributionType::ALL.iter().zip(type_accounts) {
+        let type_id = legacy_type.id();
+        let seeds: &[&[u8]] = &[
+            b"contribution_type",
+            points_config.as_ref(),
+            &[type_id],
+        ];
+
+        let bump = create_pda_account(
+            payer,
+            account,
+            system_program,
+            ContributionTypeDef::SPACE,
+            seeds,
+        )?;
+
+        let base_points = DEFAULT_BASE_POINTS[type_id as usize];
+
+        write_account(account, &ContributionTypeDef {
+            points_config,
+            type_id,
+            name: legacy_type.name().to_string(),
+            base_points,
+            is_enabled: true,
+            requires_verification: true,
+            bump,
+        })?;
+
+        emit!(ContributionTypeRegistered {
+            points_config,
+            type_id,
+            name: legacy_type.name().to_string(),
+            base_points,
+            requires_verification: true,
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct ContributionTypeRegistered {
+    pub points_config: Pubkey,
+    pub type_id: u8,
+    pub name: String,
+    pub base_points: u64,
+    pub requires_verification: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ContributionTypeUpdated {
+    pub points_config: Pubkey,
+    pub type_id: u8,
+    pub old_base_points: u64,
+    pub new_base_points: u64,
+    pub is_enabled: bool,
+    pub requires_verification: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BasePointsUpdated {
+    pub points_config: Pubkey,
+    pub old_base_points: [u64; LEGACY_CONTRIBUTION_TYPE_COUNT],
+    pub new_base_points: [u64; LEGACY_CONTRIBUTION_TYPE_COUNT],
+    pub timestamp: i64,
+}
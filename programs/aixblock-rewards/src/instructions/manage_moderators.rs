@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    moderator::{Moderator, ALL_PERMISSIONS},
+    points::PointsConfig,
+};
+use crate::errors::RewardError;
+
+#[derive(Accounts)]
+#[instruction(moderator_key: Pubkey)]
+pub struct AddModerator<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Moderator::SPACE,
+        seeds = [
+            b"moderator",
+            points_config.key().as_ref(),
+            moderator_key.as_ref(),
+        ],
+        bump
+    )]
+    pub moderator: Account<'info, Moderator>,
+
+    #[account(
+        mut,
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveModerator<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"moderator",
+            points_config.key().as_ref(),
+            moderator.authority.as_ref(),
+        ],
+        bump = moderator.bump
+    )]
+    pub moderator: Account<'info, Moderator>,
+
+    #[account(
+        mut,
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SuspendModerator<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"moderator",
+            points_config.key().as_ref(),
+            moderator.authority.as_ref(),
+        ],
+        bump = moderator.bump
+    )]
+    pub moderator: Account<'info, Moderator>,
+
+    #[account(
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> AddModerator<'info> {
+    pub fn process(
+        &mut self,
+        moderator_key: Pubkey,
+        permissions: u8,
+        bump: u8,
+    ) -> Result<()> {
+        require!(
+            permissions != 0 && permissions & !ALL_PERMISSIONS == 0,
+            RewardError::InvalidPermissions
+        );
+
+        let clock = Clock::get()?;
+
+        self.moderator.points_config = self.points_config.key();
+        self.moderator.authority = moderator_key;
+        self.moderator.permissions = permissions;
+        self.moderator.is_suspended = false;
+        self.moderator.added_at = clock.unix_timestamp;
+        self.moderator.bump = bump;
+
+        emit!(ModeratorAdded {
+            points_config: self.points_config.key(),
+            moderator: moderator_key,
+            permissions,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> RemoveModerator<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        emit!(ModeratorRemoved {
+            points_config: self.points_config.key(),
+            moderator: self.moderator.authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> SuspendModerator<'info> {
+    pub fn process(&mut self, suspended: bool) -> Result<()> {
+        self.moderator.is_suspended = suspended;
+
+        emit!(ModeratorSuspensionUpdated {
+            points_config: self.points_config.key(),
+            moderator: self.moderator.authority,
+            is_suspended: suspended,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct ModeratorAdded {
+    pub points_config: Pubkey,
+    pub moderator: Pubkey,
+    pub permissions: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ModeratorRemoved {
+    pub points_config: Pubkey,
+    pub moderator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ModeratorSuspensionUpdated {
+    pub points_config: Pubkey,
+    pub moderator: Pubkey,
+    pub is_suspended: bool,
+    pub timestamp: i64,
+}
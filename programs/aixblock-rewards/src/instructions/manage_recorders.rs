@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use crate::state::points::{PointsConfig, MAX_RECORDERS};
+use crate::errors::RewardError;
+
+#[derive(Accounts)]
+pub struct ManageRecorders<'info> {
+    #[account(mut)]
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> ManageRecorders<'info> {
+    pub fn add_recorder(&mut self, recorder: Pubkey) -> Result<()> {
+        require!(
+            !self.points_config.is_recorder(&recorder),
+            RewardError::RecorderAlreadyRegistered
+        );
+        require!(
+            self.points_config.recorders.len() < MAX_RECORDERS,
+            RewardError::RecorderListFull
+        );
+
+        self.points_config.recorders.push(recorder);
+
+        emit!(RecorderAdded {
+            points_config: self.points_config.key(),
+            recorder,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn remove_recorder(&mut self, recorder: Pubkey) -> Result<()> {
+        let index = self.points_config.recorders
+            .iter()
+            .position(|key| *key == recorder)
+            .ok_or(RewardError::RecorderNotFound)?;
+
+        self.points_config.recorders.swap_remove(index);
+
+        emit!(RecorderRemoved {
+            points_config: self.points_config.key(),
+            recorder,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn update_recorder_policy(&mut self, self_submission_max_impact: u8) -> Result<()> {
+        self.points_config.self_submission_max_impact = self_submission_max_impact;
+
+        emit!(RecorderPolicyUpdated {
+            points_config: self.points_config.key(),
+            self_submission_max_impact,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn update_attestation_agent(&mut self, agent: Pubkey) -> Result<()> {
+        let old_agent = self.points_config.attestation_agent;
+        self.points_config.attestation_agent = agent;
+
+        emit!(AttestationAgentUpdated {
+            points_config: self.points_config.key(),
+            old_agent,
+            new_agent: agent,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct RecorderAdded {
+    pub points_config: Pubkey,
+    pub recorder: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecorderRemoved {
+    pub points_config: Pubkey,
+    pub recorder: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecorderPolicyUpdated {
+    pub points_config: Pubkey,
+    pub self_submission_max_impact: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttestationAgentUpdated {
+    pub points_config: Pubkey,
+    pub old_agent: Pubkey,
+    pub new_agent: Pubkey,
+    pub timestamp: i64,
+}
@@ -1,37 +1,120 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::points::PointsConfig;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{
+    moderator::{Moderator, PERMISSION_MANAGE_RESERVE},
+    points::PointsConfig,
+    reserve::ReserveState,
+};
 use crate::errors::RewardError;
+use crate::utils::transfer_checked_net;
+use crate::MIN_RESERVE_WITHDRAWAL_DELAY;
 
 #[derive(Accounts)]
-pub struct ManageReserve<'info> {
-    #[account(mut)]
+pub struct InitializeReserveState<'info> {
     pub points_config: Account<'info, PointsConfig>,
 
+    #[account(
+        init,
+        payer = authority,
+        space = ReserveState::SPACE,
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+
+    // Tokens already in the vault open the ledger balance
+    #[account(address = points_config.reserve_vault)]
+    pub reserve_vault: InterfaceAccount<'info, TokenAccount>,
+
     #[account(
         mut,
-        constraint = reserve_vault.owner == reserve_vault_authority.key(),
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
     )]
-    pub reserve_vault: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Books tokens that reached the reserve vault outside the program, such as
+// direct transfers, so the ledger balance matches the vault again
+#[derive(Accounts)]
+pub struct ReconcileReserve<'info> {
+    pub points_config: Account<'info, PointsConfig>,
 
     #[account(
         mut,
-        constraint = distribution_vault.owner == distribution_vault_authority.key(),
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+
+    #[account(address = points_config.reserve_vault)]
+    pub reserve_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [
+            b"moderator",
+            points_config.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = moderator.bump,
+        constraint = !moderator.is_suspended @ RewardError::ModeratorSuspended,
+        constraint = moderator.has_permission(PERMISSION_MANAGE_RESERVE) @ RewardError::Unauthorized,
     )]
-    pub distribution_vault: Account<'info, TokenAccount>,
+    pub moderator: Account<'info, Moderator>,
 
-    /// CHECK: PDA for reserve vault
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ManageReserve<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+
+    #[account(address = points_config.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = points_config.reserve_vault)]
+    pub reserve_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = points_config.distribution_vault)]
+    pub distribution_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for reserve vault authority
+    #[account(
+        seeds = [b"reserve_authority", points_config.key().as_ref()],
+        bump = points_config.reserve_authority_bump
+    )]
     pub reserve_vault_authority: AccountInfo<'info>,
 
-    /// CHECK: PDA for distribution vault
+    /// CHECK: PDA for distribution vault authority
+    #[account(
+        seeds = [b"distribution_authority", points_config.key().as_ref()],
+        bump = points_config.distribution_authority_bump
+    )]
     pub distribution_vault_authority: AccountInfo<'info>,
 
     #[account(
-        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+        seeds = [
+            b"moderator",
+            points_config.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = moderator.bump,
+        constraint = !moderator.is_suspended @ RewardError::ModeratorSuspended,
+        constraint = moderator.has_permission(PERMISSION_MANAGE_RESERVE) @ RewardError::Unauthorized,
     )]
+    pub moderator: Account<'info, Moderator>,
+
     pub authority: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
@@ -41,21 +124,148 @@ pub struct UpdateReserveConfig<'info> {
     pub points_config: Account<'info, PointsConfig>,
 
     #[account(
-        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+        seeds = [
+            b"moderator",
+            points_config.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = moderator.bump,
+        constraint = !moderator.is_suspended @ RewardError::ModeratorSuspended,
+        constraint = moderator.has_permission(PERMISSION_MANAGE_RESERVE) @ RewardError::Unauthorized,
     )]
+    pub moderator: Account<'info, Moderator>,
+
     pub authority: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
+// Release policy changes wait out the reserve withdrawal delay, so the
+// authority or guardian can cancel one before it moves reserve tokens
+#[derive(Accounts)]
+pub struct ProposeReserveReleasePolicy<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+
+    #[account(
+        seeds = [
+            b"moderator",
+            points_config.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = moderator.bump,
+        constraint = !moderator.is_suspended @ RewardError::ModeratorSuspended,
+        constraint = moderator.has_permission(PERMISSION_MANAGE_RESERVE) @ RewardError::Unauthorized,
+    )]
+    pub moderator: Account<'info, Moderator>,
+
+    pub authority: Signer<'info>,
+}
+
+// Permissionless once the policy timelock has passed
+#[derive(Accounts)]
+pub struct ApplyReserveReleasePolicy<'info> {
+    #[account(mut)]
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+}
+
+// The config authority or the reserve guardian may cancel
+#[derive(Accounts)]
+pub struct CancelReserveReleasePolicy<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump,
+        constraint = reserve_state.can_cancel_withdrawal(
+            &authority.key(),
+            &points_config.authority,
+        ) @ RewardError::Unauthorized,
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+
+    pub authority: Signer<'info>,
+}
+
+impl<'info> InitializeReserveState<'info> {
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        self.reserve_state.points_config = self.points_config.key();
+        self.reserve_state.balance = self.reserve_vault.amount;
+        self.reserve_state.total_inflows = self.reserve_vault.amount;
+        self.reserve_state.total_outflows = 0;
+        self.reserve_state.period_contributions = Vec::new();
+        self.reserve_state.guardian = Pubkey::default();
+        self.reserve_state.withdrawal_delay = MIN_RESERVE_WITHDRAWAL_DELAY;
+        self.reserve_state.withdrawal_limit = 0;
+        self.reserve_state.withdrawal_period = self.points_config.current_period;
+        self.reserve_state.withdrawn_in_period = 0;
+        self.reserve_state.next_withdrawal_id = 0;
+        self.reserve_state.clear_pending_policy();
+        self.reserve_state.clear_pending_release_policy();
+        self.reserve_state.bump = bump;
+
+        emit!(ReserveStateInitialized {
+            points_config: self.points_config.key(),
+            reserve_state: self.reserve_state.key(),
+            opening_balance: self.reserve_state.balance,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ReconcileReserve<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let vault_balance = self.reserve_vault.amount;
+
+        // Every program outflow is booked, so the vault never holds less
+        let surplus = vault_balance
+            .checked_sub(self.reserve_state.balance)
+            .ok_or(RewardError::ReserveCalculationError)?;
+        if surplus == 0 {
+            return Ok(());
+        }
+
+        let (balance_before, balance_after) = self.reserve_state.record_inflow(
+            surplus,
+            self.points_config.current_period,
+        )?;
+
+        emit!(ReserveReconciled {
+            points_config: self.points_config.key(),
+            amount: surplus,
+            balance_before,
+            balance_after,
+            period: self.points_config.current_period,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
 impl<'info> ManageReserve<'info> {
     pub fn process_reserve_transfer(
         &mut self,
         amount: u64,
-        vault_authority_bump: u8,
     ) -> Result<()> {
         // Verify reserve has sufficient balance
-        if self.reserve_vault.amount < amount {
+        if self.reserve_vault.amount < amount || self.reserve_state.balance < amount {
             return Err(RewardError::InsufficientBalance.into());
         }
 
@@ -67,31 +277,29 @@ impl<'info> ManageReserve<'info> {
         let seeds = &[
             b"reserve_authority".as_ref(),
             points_config_pubkey.as_ref(),
-            &[vault_authority_bump],
+            &[self.points_config.reserve_authority_bump],
         ];
         let signer_seeds = &[&seeds[..]];
 
-        let transfer_ix = Transfer {
-            from: self.reserve_vault.to_account_info(),
-            to: self.distribution_vault.to_account_info(),
-            authority: self.reserve_vault_authority.to_account_info(),
-        };
-
-        token::transfer(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                transfer_ix,
-                signer_seeds,
-            ),
+        let received = transfer_checked_net(
+            &self.token_program,
+            &self.reserve_vault,
+            &mut self.distribution_vault,
+            &self.reward_mint,
+            &self.reserve_vault_authority,
             amount,
+            signer_seeds,
         )?;
 
-        // Update points config state
-        self.points_config.update_period_points(amount)?;
+        // Book the outflow on the reserve ledger
+        let (balance_before, balance_after) = self.reserve_state.record_outflow(amount)?;
 
         // Emit event
         emit!(ReserveTransfer {
-            amount,
+            amount: received,
+            fee: amount - received,
+            balance_before,
+            balance_after,
             timestamp: clock.unix_timestamp,
             from_reserve: self.reserve_vault.key(),
             to_distribution: self.distribution_vault.key(),
@@ -103,7 +311,6 @@ impl<'info> ManageReserve<'info> {
     pub fn process_add_to_reserve(
         &mut self,
         amount: u64,
-        vault_authority_bump: u8,
     ) -> Result<()> {
         // Store points config pubkey for seeds
         let points_config_pubkey = self.points_config.key();
@@ -112,31 +319,32 @@ impl<'info> ManageReserve<'info> {
         let seeds = &[
             b"distribution_authority".as_ref(),
             points_config_pubkey.as_ref(),
-            &[vault_authority_bump],
+            &[self.points_config.distribution_authority_bump],
         ];
         let signer_seeds = &[&seeds[..]];
 
-        let transfer_ix = Transfer {
-            from: self.distribution_vault.to_account_info(),
-            to: self.reserve_vault.to_account_info(),
-            authority: self.distribution_vault_authority.to_account_info(),
-        };
-
-        token::transfer(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                transfer_ix,
-                signer_seeds,
-            ),
+        let received = transfer_checked_net(
+            &self.token_program,
+            &self.distribution_vault,
+            &mut self.reserve_vault,
+            &self.reward_mint,
+            &self.distribution_vault_authority,
             amount,
+            signer_seeds,
         )?;
 
         // Update reserve stats
-        self.points_config.update_reserve(amount)?;
+        let (balance_before, balance_after) = self.reserve_state.record_inflow(
+            received,
+            self.points_config.current_period,
+        )?;
 
         // Emit event
         emit!(ReserveDeposit {
-            amount,
+            amount: received,
+            fee: amount - received,
+            balance_before,
+            balance_after,
             timestamp: clock.unix_timestamp,
             new_reserve_balance: self.reserve_vault.amount,
         });
@@ -175,9 +383,65 @@ impl<'info> UpdateReserveConfig<'info> {
     }
 }
 
+impl<'info> ProposeReserveReleasePolicy<'info> {
+    pub fn process(&mut self, release_bps: u16, release_cap: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let effective_at = self.reserve_state.propose_release_policy(release_bps, release_cap, now)?;
+
+        emit!(ReserveReleasePolicyProposed {
+            release_bps,
+            release_cap,
+            effective_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ApplyReserveReleasePolicy<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let (release_bps, release_cap) = self.reserve_state.take_release_policy(now)?;
+
+        self.points_config.reserve_release_bps = release_bps;
+        self.points_config.reserve_release_cap = release_cap;
+
+        emit!(ReserveReleasePolicyUpdated {
+            release_bps,
+            release_cap,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> CancelReserveReleasePolicy<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        require!(
+            self.reserve_state.has_pending_release_policy(),
+            RewardError::PolicyChangeNotPending
+        );
+
+        self.reserve_state.clear_pending_release_policy();
+
+        emit!(ReserveReleasePolicyCancelled {
+            cancelled_by: self.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
 #[event]
 pub struct ReserveTransfer {
+    // Net amount received after transfer fees
     pub amount: u64,
+    pub fee: u64,
+    pub balance_before: u64,
+    pub balance_after: u64,
     pub timestamp: i64,
     pub from_reserve: Pubkey,
     pub to_distribution: Pubkey,
@@ -185,7 +449,11 @@ pub struct ReserveTransfer {
 
 #[event]
 pub struct ReserveDeposit {
+    // Net amount received after transfer fees
     pub amount: u64,
+    pub fee: u64,
+    pub balance_before: u64,
+    pub balance_after: u64,
     pub timestamp: i64,
     pub new_reserve_balance: u64,
 }
@@ -195,4 +463,43 @@ pub struct ReserveConfigUpdated {
     pub reserve_ratio: u16,
     pub monthly_threshold: u64,
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct ReserveStateInitialized {
+    pub points_config: Pubkey,
+    pub reserve_state: Pubkey,
+    pub opening_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveReconciled {
+    pub points_config: Pubkey,
+    pub amount: u64,
+    pub balance_before: u64,
+    pub balance_after: u64,
+    pub period: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveReleasePolicyProposed {
+    pub release_bps: u16,
+    pub release_cap: u64,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveReleasePolicyCancelled {
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveReleasePolicyUpdated {
+    pub release_bps: u16,
+    pub release_cap: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,513 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{
+    contributor::Contributor,
+    distribution::{ClaimBitmap, DistributionPeriod, MAX_MERKLE_LEAVES},
+    points::PointsConfig,
+    reserve::ReserveState,
+    vesting::VestingEscrow,
+};
+use crate::errors::RewardError;
+use crate::instructions::distribute_tokens::{PeriodDustSwept, TokensVested};
+use crate::utils::transfer_checked_net;
+
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32], total_amount: u64, num_leaves: u32)]
+pub struct PublishMerkleRoot<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"distribution",
+            points_config.key().as_ref(),
+            &distribution_period.period.to_le_bytes(),
+        ],
+        bump = distribution_period.bump
+    )]
+    pub distribution_period: Account<'info, DistributionPeriod>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ClaimBitmap::space(num_leaves),
+        seeds = [b"claim_bitmap", distribution_period.key().as_ref()],
+        bump
+    )]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+
+    #[account(
+        mut,
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u32, amount: u64, proof: Vec<[u8; 32]>)]
+pub struct ClaimWithProof<'info> {
+    #[account(mut)]
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"distribution",
+            points_config.key().as_ref(),
+            &distribution_period.period.to_le_bytes(),
+        ],
+        bump = distribution_period.bump,
+        constraint = distribution_period.has_merkle_root() @ RewardError::InvalidMerkleDistribution,
+    )]
+    pub distribution_period: Account<'info, DistributionPeriod>,
+
+    #[account(
+        mut,
+        seeds = [b"claim_bitmap", distribution_period.key().as_ref()],
+        bump = claim_bitmap.bump
+    )]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+
+    #[account(address = points_config.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = points_config.reward_vault)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = claimant_token_account.mint == reward_vault.mint,
+        constraint = contributor.is_payout_destination(
+            &claimant_token_account.key(),
+            &reward_mint.key(),
+            &token_program.key(),
+        ) @ RewardError::InvalidPayoutDestination,
+    )]
+    pub claimant_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for reward vault authority
+    #[account(
+        seeds = [b"vault_authority", points_config.key().as_ref()],
+        bump = points_config.vault_authority_bump
+    )]
+    pub reward_vault_authority: AccountInfo<'info>,
+
+    // Receives whatever the tree left out of the pool once every leaf is claimed
+    #[account(mut, address = points_config.reserve_vault)]
+    pub reserve_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+
+    // The leaf is committed to the contributor's wallet
+    #[account(
+        mut,
+        seeds = [b"contributor", contributor.authority.as_ref()],
+        bump = contributor.bump,
+    )]
+    pub contributor: Account<'info, Contributor>,
+
+    // Holds the locked share of the leaf amount; only needed while the
+    // config's vesting policy locks part of it
+    #[account(
+        init,
+        payer = payer,
+        space = VestingEscrow::SPACE,
+        seeds = [
+            b"vesting_escrow",
+            points_config.key().as_ref(),
+            contributor.key().as_ref(),
+            &distribution_period.period.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub vesting_escrow: Option<Account<'info, VestingEscrow>>,
+
+    #[account(mut, address = points_config.vesting_vault)]
+    pub vesting_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // Any cranker may submit the proof and pay the escrow rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PublishMerkleRoot<'info> {
+    pub fn process(
+        &mut self,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        num_leaves: u32,
+        bitmap_bump: u8,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let period = &mut self.distribution_period;
+
+        // A period is paid either by proof or through distribute_tokens, never both
+        require!(!period.has_merkle_root(), RewardError::MerkleRootAlreadySet);
+        require!(period.tokens_distributed == 0, RewardError::DistributionAlreadyProcessed);
+        period.validate_claimable(now)?;
+
+        // Carried points already left the period, so a tree built from its
+        // snapshot would pay them twice
+        require!(period.points_carried == 0, RewardError::InvalidMerkleDistribution);
+
+        // The per-leaf amounts are not checked against the points on-chain:
+        // the authority is trusted to publish the capped allocation, and only
+        // the tree total is bounded by the pool here
+        require!(
+            merkle_root != [0u8; 32]
+                && num_leaves > 0
+                && num_leaves <= MAX_MERKLE_LEAVES
+                && total_amount <= period.total_tokens,
+            RewardError::InvalidMerkleDistribution
+        );
+
+        period.merkle_root = merkle_root;
+        period.merkle_total_amount = total_amount;
+
+        self.claim_bitmap.distribution_period = period.key();
+        self.claim_bitmap.num_leaves = num_leaves;
+        self.claim_bitmap.num_claimed = 0;
+        self.claim_bitmap.bits = vec![0u8; ClaimBitmap::bitmap_len(num_leaves)];
+        self.claim_bitmap.bump = bitmap_bump;
+
+        emit!(MerkleRootPublished {
+            period: period.period,
+            merkle_root,
+            total_amount,
+            num_leaves,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ClaimWithProof<'info> {
+    pub fn process(
+        &mut self,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+        bumps: &ClaimWithProofBumps,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        self.distribution_period.validate_claimable(now)?;
+
+        require!(
+            index < self.claim_bitmap.num_leaves && amount > 0,
+            RewardError::InvalidMerkleDistribution
+        );
+        require!(!self.claim_bitmap.is_claimed(index), RewardError::AlreadyClaimed);
+
+        // The tree may redistribute capped excess but never exceed the cap
+        require!(
+            self.distribution_period.max_payout == 0
+                || amount <= self.distribution_period.max_payout,
+            RewardError::InvalidMerkleDistribution
+        );
+
+        let leaf = merkle_leaf(index, &self.contributor.authority, amount);
+        require!(
+            verify_merkle_proof(&proof, self.distribution_period.merkle_root, leaf),
+            RewardError::InvalidMerkleProof
+        );
+
+        // Never pay out more than the published tree commits to
+        let tokens_distributed = self.distribution_period.tokens_distributed
+            .checked_add(amount)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+        require!(
+            tokens_distributed <= self.distribution_period.merkle_total_amount,
+            RewardError::InsufficientBalance
+        );
+
+        let points_config_pubkey = self.points_config.key();
+        let seeds = &[
+            b"vault_authority".as_ref(),
+            points_config_pubkey.as_ref(),
+            &[self.points_config.vault_authority_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Proof payouts follow the same vesting policy as distribute_tokens
+        let (immediate, locked) = self.points_config.split_vested_payout(amount)?;
+
+        let released = if immediate > 0 {
+            transfer_checked_net(
+                &self.token_program,
+                &self.reward_vault,
+                &mut self.claimant_token_account,
+                &self.reward_mint,
+                &self.reward_vault_authority,
+                immediate,
+                signer_seeds,
+            )?
+        } else {
+            0
+        };
+
+        let locked = if locked > 0 {
+            require!(self.vesting_escrow.is_some(), RewardError::VestingAccountsRequired);
+            let vesting_vault = self.vesting_vault
+                .as_mut()
+                .ok_or(RewardError::VestingAccountsRequired)?;
+
+            transfer_checked_net(
+                &self.token_program,
+                &self.reward_vault,
+                vesting_vault,
+                &self.reward_mint,
+                &self.reward_vault_authority,
+                locked,
+                signer_seeds,
+            )?
+        } else {
+            0
+        };
+
+        let received = released
+            .checked_add(locked)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        if let Some(vesting_escrow) = self.vesting_escrow.as_mut() {
+            vesting_escrow.open(
+                &self.points_config,
+                self.contributor.key(),
+                self.distribution_period.period,
+                locked,
+                now,
+                bumps.vesting_escrow.ok_or(RewardError::VestingAccountsRequired)?,
+            )?;
+
+            emit!(TokensVested {
+                contributor: self.contributor.key(),
+                period: self.distribution_period.period,
+                released,
+                locked,
+                cliff_time: vesting_escrow.cliff_time,
+                end_time: vesting_escrow.end_time,
+            });
+        }
+
+        self.contributor.tokens_claimed = self.contributor.tokens_claimed
+            .checked_add(received)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+        self.contributor.last_claim_time = now;
+
+        self.claim_bitmap.set_claimed(index);
+        self.claim_bitmap.num_claimed = self.claim_bitmap.num_claimed
+            .checked_add(1)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        self.distribution_period.tokens_distributed = tokens_distributed;
+        self.points_config.release_committed_rewards(amount);
+
+        // Once every leaf is claimed, whatever the tree left out of the pool
+        // goes to the reserve
+        if self.claim_bitmap.num_claimed == self.claim_bitmap.num_leaves {
+            let period = self.distribution_period.period;
+            let dust = self.distribution_period.unsettled_tokens();
+
+            if dust > 0 {
+                let received = transfer_checked_net(
+                    &self.token_program,
+                    &self.reward_vault,
+                    &mut self.reserve_vault,
+                    &self.reward_mint,
+                    &self.reward_vault_authority,
+                    dust,
+                    signer_seeds,
+                )?;
+
+                let (balance_before, balance_after) =
+                    self.reserve_state.record_inflow(received, period)?;
+
+                emit!(PeriodDustSwept {
+                    period,
+                    amount: received,
+                    balance_before,
+                    balance_after,
+                    timestamp: now,
+                });
+            }
+
+            self.points_config.release_committed_rewards(dust);
+            self.distribution_period.dust_swept = dust;
+            self.distribution_period.is_completed = true;
+        }
+
+        emit!(MerkleClaimed {
+            period: self.distribution_period.period,
+            claimant: self.contributor.authority,
+            recipient: self.claimant_token_account.key(),
+            mint: self.reward_mint.key(),
+            index,
+            amount: received,
+            fee: amount - received,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+// Leaf committed to by the off-chain tree: keccak(index || claimant || amount)
+pub fn merkle_leaf(index: u32, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    keccak::hashv(&[
+        &index.to_le_bytes(),
+        claimant.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .0
+}
+
+// Walks the proof from the leaf up, hashing each pair in sorted order so
+// the proof does not need to encode left/right positions
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).0
+        } else {
+            keccak::hashv(&[sibling, &node]).0
+        }
+    });
+
+    computed == root
+}
+
+#[event]
+pub struct MerkleRootPublished {
+    pub period: u16,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub num_leaves: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MerkleClaimed {
+    pub period: u16,
+    pub claimant: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub index: u32,
+    // Net amount received after transfer fees
+    pub amount: u64,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            keccak::hashv(&[&a, &b]).0
+        } else {
+            keccak::hashv(&[&b, &a]).0
+        }
+    }
+
+    // Four-leaf tree; returns the root and the proof for every leaf
+    fn build_tree(leaves: [[u8; 32]; 4]) -> ([u8; 32], Vec<Vec<[u8; 32]>>) {
+        let left = hash_pair(leaves[0], leaves[1]);
+        let right = hash_pair(leaves[2], leaves[3]);
+        let root = hash_pair(left, right);
+
+        let proofs = vec![
+            vec![leaves[1], right],
+            vec![leaves[0], right],
+            vec![leaves[3], left],
+            vec![leaves[2], left],
+        ];
+
+        (root, proofs)
+    }
+
+    fn sample_leaves() -> ([Pubkey; 4], [[u8; 32]; 4]) {
+        let claimants = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let leaves = [
+            merkle_leaf(0, &claimants[0], 100),
+            merkle_leaf(1, &claimants[1], 200),
+            merkle_leaf(2, &claimants[2], 300),
+            merkle_leaf(3, &claimants[3], 400),
+        ];
+
+        (claimants, leaves)
+    }
+
+    #[test]
+    fn every_leaf_verifies_against_the_root() {
+        let (_, leaves) = sample_leaves();
+        let (root, proofs) = build_tree(leaves);
+
+        for (leaf, proof) in leaves.iter().zip(&proofs) {
+            assert!(verify_merkle_proof(proof, root, *leaf));
+        }
+    }
+
+    #[test]
+    fn single_leaf_tree_needs_no_proof() {
+        let leaf = merkle_leaf(0, &Pubkey::new_unique(), 1_000);
+
+        assert!(verify_merkle_proof(&[], leaf, leaf));
+    }
+
+    #[test]
+    fn proof_for_another_leaf_is_rejected() {
+        let (_, leaves) = sample_leaves();
+        let (root, proofs) = build_tree(leaves);
+
+        assert!(!verify_merkle_proof(&proofs[1], root, leaves[2]));
+    }
+
+    #[test]
+    fn proof_with_siblings_out_of_order_is_rejected() {
+        let (_, leaves) = sample_leaves();
+        let (root, proofs) = build_tree(leaves);
+
+        let mut reordered = proofs[0].clone();
+        reordered.reverse();
+        assert!(!verify_merkle_proof(&reordered, root, leaves[0]));
+    }
+
+    #[test]
+    fn tampered_amount_or_index_is_rejected() {
+        let (claimants, leaves) = sample_leaves();
+        let (root, proofs) = build_tree(leaves);
+
+        let inflated = merkle_leaf(0, &claimants[0], 101);
+        assert!(!verify_merkle_proof(&proofs[0], root, inflated));
+
+        let moved = merkle_leaf(1, &claimants[0], 100);
+        assert!(!verify_merkle_proof(&proofs[0], root, moved));
+
+        let stolen = merkle_leaf(0, &claimants[1], 100);
+        assert!(!verify_merkle_proof(&proofs[0], root, stolen));
+    }
+
+    #[test]
+    fn truncated_proof_is_rejected() {
+        let (_, leaves) = sample_leaves();
+        let (root, proofs) = build_tree(leaves);
+
+        assert!(!verify_merkle_proof(&proofs[0][..1], root, leaves[0]));
+    }
+}
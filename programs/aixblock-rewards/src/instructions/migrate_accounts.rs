@@ -0,0 +1,277 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::state::{
+    contribution::{Contribution, LEGACY_CONTRIBUTION_TYPE_COUNT},
+    contributor::Contributor,
+    metadata_receipt::MetadataReceipt,
+    points::{PointsConfig, DEFAULT_BASE_POINTS},
+};
+use crate::errors::RewardError;
+use crate::instructions::manage_contribution_types::register_legacy_contribution_types;
+use crate::instructions::record_contribution::load_period_points;
+use crate::program::AixblockRewards;
+use crate::utils::{create_pda_account, realloc_account, write_account};
+
+// Accounts created before fields were appended to PointsConfig, Contributor
+// and Contribution keep their original prefix; these instructions grow them
+// to the current size. Appended fields start zeroed unless set below.
+//
+// Legacy contributors and contributions do not record which config they
+// belong to, so only the program upgrade authority may bind them to one.
+
+// Remaining accounts: the ContributionTypeDef PDAs for every legacy
+// ContributionType, in enum order
+#[derive(Accounts)]
+pub struct MigratePointsConfig<'info> {
+    /// CHECK: legacy-sized PointsConfig; discriminator, address and authority are checked in process
+    #[account(mut, owner = crate::ID)]
+    pub points_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Moves the legacy monthly points into the config's current period
+#[derive(Accounts)]
+pub struct MigrateContributor<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    /// CHECK: legacy-sized Contributor; discriminator and address are checked in process
+    #[account(mut, owner = crate::ID)]
+    pub contributor: UncheckedAccount<'info>,
+
+    /// CHECK: ContributorPeriodPoints PDA for the current period, created by load_period_points
+    #[account(mut)]
+    pub period_points: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ RewardError::Unauthorized
+    )]
+    pub program: Program<'info, AixblockRewards>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ RewardError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Binds a legacy contribution to the config it was recorded against
+#[derive(Accounts)]
+pub struct MigrateContribution<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    /// CHECK: legacy-sized Contribution; discriminator is checked in process
+    #[account(mut, owner = crate::ID)]
+    pub contribution: UncheckedAccount<'info>,
+
+    /// CHECK: MetadataReceipt PDA for the artifact, created in process unless
+    /// another legacy contribution already claimed it
+    #[account(mut)]
+    pub metadata_receipt: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ RewardError::Unauthorized
+    )]
+    pub program: Program<'info, AixblockRewards>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ RewardError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigratePointsConfig<'info> {
+    pub fn process(&mut self, type_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let info = self.points_config.to_account_info();
+        let old_space = check_legacy_account(&info, &PointsConfig::DISCRIMINATOR, PointsConfig::SPACE)?;
+
+        // The authority sits right after the discriminator in every layout
+        let stored_authority = Pubkey::try_from(&info.try_borrow_data()?[8..40])
+            .map_err(|_| RewardError::InvalidAccountAddress)?;
+        require_keys_eq!(stored_authority, self.authority.key(), RewardError::Unauthorized);
+
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"points_config", stored_authority.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(info.key(), expected, RewardError::InvalidAccountAddress);
+
+        realloc_account(
+            &self.authority.to_account_info(),
+            &info,
+            &self.system_program.to_account_info(),
+            PointsConfig::SPACE,
+        )?;
+
+        let mut points_config = PointsConfig::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        points_config.emission_start_period = points_config.current_period;
+        write_account(&info, &points_config)?;
+
+        // Legacy contributions keep their enum value as the type id
+        register_legacy_contribution_types(
+            info.key(),
+            &self.authority.to_account_info(),
+            &self.system_program.to_account_info(),
+            type_accounts,
+        )?;
+
+        emit_migrated(info.key(), old_space, PointsConfig::SPACE)
+    }
+}
+
+impl<'info> MigrateContributor<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let info = self.contributor.to_account_info();
+        let old_space = check_legacy_account(&info, &Contributor::DISCRIMINATOR, Contributor::SPACE)?;
+
+        let stored_authority = Pubkey::try_from(&info.try_borrow_data()?[8..40])
+            .map_err(|_| RewardError::InvalidAccountAddress)?;
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"contributor", stored_authority.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(info.key(), expected, RewardError::InvalidAccountAddress);
+
+        realloc_account(
+            &self.authority.to_account_info(),
+            &info,
+            &self.system_program.to_account_info(),
+            Contributor::SPACE,
+        )?;
+
+        // Legacy monthly points were credited when recorded and still count
+        // toward the config's current period totals, so they become
+        // claimable for that period
+        let mut contributor = Contributor::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        let period = self.points_config.current_period;
+        contributor.points_period = period;
+
+        if contributor.current_month_points > 0 {
+            let points_info = self.period_points.to_account_info();
+            let mut period_points = load_period_points(
+                &self.authority.to_account_info(),
+                &points_info,
+                &self.system_program.to_account_info(),
+                self.points_config.key(),
+                info.key(),
+                period,
+            )?;
+            period_points.credit(contributor.current_month_points)?;
+            write_account(&points_info, &period_points)?;
+        }
+
+        write_account(&info, &contributor)?;
+
+        emit_migrated(info.key(), old_space, Contributor::SPACE)
+    }
+}
+
+impl<'info> MigrateContribution<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let info = self.contribution.to_account_info();
+        let old_space = check_legacy_account(&info, &Contribution::DISCRIMINATOR, Contribution::SPACE)?;
+
+        realloc_account(
+            &self.authority.to_account_info(),
+            &info,
+            &self.system_program.to_account_info(),
+            Contribution::SPACE,
+        )?;
+
+        let mut contribution = Contribution::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        contribution.points_config = self.points_config.key();
+
+        // Legacy points were credited when the contribution was recorded
+        contribution.is_verified = true;
+
+        // Legacy points were base points times the impact score, so the
+        // score can be recovered from the legacy base point table
+        if (contribution.type_id as usize) < LEGACY_CONTRIBUTION_TYPE_COUNT {
+            let base_points = DEFAULT_BASE_POINTS[contribution.type_id as usize];
+            contribution.impact_score = (contribution.points / base_points).clamp(1, 5) as u8;
+        }
+
+        write_account(&info, &contribution)?;
+        self.claim_metadata(&contribution, info.key())?;
+
+        emit_migrated(info.key(), old_space, Contribution::SPACE)
+    }
+
+    // Claims the artifact so it cannot be recorded again under the config
+    fn claim_metadata(&self, contribution: &Contribution, contribution_key: Pubkey) -> Result<()> {
+        let receipt_info = self.metadata_receipt.to_account_info();
+        let points_config = self.points_config.key();
+        let type_id = [contribution.type_id];
+        let seeds: &[&[u8]] = &[
+            b"metadata_receipt",
+            points_config.as_ref(),
+            &type_id,
+            contribution.metadata.as_ref(),
+        ];
+
+        if !receipt_info.data_is_empty() {
+            let (expected, _) = Pubkey::find_program_address(seeds, &crate::ID);
+            require_keys_eq!(receipt_info.key(), expected, RewardError::InvalidAccountAddress);
+            return Ok(());
+        }
+
+        let bump = create_pda_account(
+            &self.authority.to_account_info(),
+            &receipt_info,
+            &self.system_program.to_account_info(),
+            MetadataReceipt::SPACE,
+            seeds,
+        )?;
+
+        write_account(&receipt_info, &MetadataReceipt {
+            points_config,
+            contribution: contribution_key,
+            type_id: contribution.type_id,
+            metadata: contribution.metadata,
+            created_at: contribution.timestamp,
+            bump,
+        })
+    }
+}
+
+// Returns the current size of a legacy account of the expected type
+fn check_legacy_account(info: &AccountInfo, discriminator: &[u8; 8], space: usize) -> Result<usize> {
+    let data = info.try_borrow_data()?;
+    require!(
+        data.len() >= 8 && data[..8] == discriminator[..],
+        RewardError::InvalidAccountAddress
+    );
+    require!(data.len() < space, RewardError::AccountAlreadyMigrated);
+    Ok(data.len())
+}
+
+fn emit_migrated(account: Pubkey, old_space: usize, new_space: usize) -> Result<()> {
+    emit!(AccountMigrated {
+        account,
+        old_space: old_space as u32,
+        new_space: new_space as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub old_space: u32,
+    pub new_space: u32,
+    pub timestamp: i64,
+}
@@ -1,21 +1,52 @@
 pub mod record_contribution;
+pub mod record_attested_contribution;
+pub mod record_contributions_batch;
 pub mod calculate_points;
 pub mod distribute_tokens;
+pub mod merkle_distribution;
+pub mod vesting;
+pub mod sweep_expired_period;
+pub mod reward_pools;
+pub mod emission_schedule;
+pub mod manage_beneficiary;
+pub mod initialize_vaults;
 pub mod manage_reserve;
+pub mod reserve_withdrawal;
+pub mod verify_contribution;
+pub mod manage_moderators;
+pub mod manage_recorders;
+pub mod manage_contribution_types;
+pub mod migrate_accounts;
 
 pub use record_contribution::*;
+pub use record_attested_contribution::*;
+pub use record_contributions_batch::*;
 pub use calculate_points::*;
 pub use distribute_tokens::*;
+pub use merkle_distribution::*;
+pub use vesting::*;
+pub use sweep_expired_period::*;
+pub use reward_pools::*;
+pub use emission_schedule::*;
+pub use manage_beneficiary::*;
+pub use initialize_vaults::*;
 pub use manage_reserve::*;
+pub use reserve_withdrawal::*;
+pub use verify_contribution::*;
+pub use manage_moderators::*;
+pub use manage_recorders::*;
+pub use manage_contribution_types::*;
+pub use migrate_accounts::*;
 
 use anchor_lang::prelude::*;
-use crate::state::{Contributor, PointsConfig};
+use crate::state::{Contributor, Moderator, PointsConfig, ALL_PERMISSIONS};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct InitializeArgs {
     pub monthly_threshold: u64,
     pub reserve_ratio: u16,
     pub max_points_per_type: u64,
+    pub self_submission_max_impact: u8,
 }
 
 #[derive(Accounts)]
@@ -30,6 +61,20 @@ pub struct Initialize<'info> {
     )]
     pub points_config: Account<'info, PointsConfig>,
 
+    // The config authority starts as a moderator with every permission
+    #[account(
+        init,
+        payer = authority,
+        space = Moderator::SPACE,
+        seeds = [
+            b"moderator",
+            points_config.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub moderator: Account<'info, Moderator>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -54,7 +99,12 @@ pub struct CreateContributor<'info> {
 }
 
 impl Initialize<'_> {
-    pub fn process(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
+    // Remaining accounts: the ContributionTypeDef PDAs for every legacy
+    // ContributionType, in enum order
+    pub fn process<'info>(
+        ctx: Context<'_, '_, '_, 'info, Initialize<'info>>,
+        args: InitializeArgs,
+    ) -> Result<()> {
         let points_config = &mut ctx.accounts.points_config;
         let clock = Clock::get()?;
         
@@ -62,11 +112,49 @@ impl Initialize<'_> {
         points_config.monthly_threshold = args.monthly_threshold;
         points_config.reserve_ratio = args.reserve_ratio;
         points_config.max_points_per_type = args.max_points_per_type;
+        points_config.self_submission_max_impact = args.self_submission_max_impact;
+        points_config.recorders = Vec::new();
+        points_config.attestation_agent = Pubkey::default();
+        points_config.vesting_cliff = 0;
+        points_config.vesting_duration = 0;
+        points_config.vesting_immediate_bps = 0;
+        points_config.claim_window = 0;
+        points_config.carry_forward_enabled = false;
+        points_config.carry_forward_min_payout = 0;
+        points_config.carry_forward_decay_bps = 0;
+        points_config.emission_per_period = 0;
+        points_config.emission_decay_bps = 0;
+        points_config.emission_decay_interval = 0;
+        points_config.emission_cap = 0;
+        points_config.emission_start_period = 1;
+        points_config.total_emitted = 0;
+        points_config.committed_rewards = 0;
+        points_config.max_share_bps = 0;
+        points_config.redistribute_excess = false;
+        points_config.reserve_release_bps = 0;
+        points_config.reserve_release_cap = 0;
+        points_config.reward_mint = Pubkey::default();
+        points_config.reward_vault = Pubkey::default();
+        points_config.reserve_vault = Pubkey::default();
+        points_config.distribution_vault = Pubkey::default();
+        points_config.vesting_vault = Pubkey::default();
+        points_config.vault_authority_bump = 0;
+        points_config.reserve_authority_bump = 0;
+        points_config.distribution_authority_bump = 0;
+        points_config.vesting_authority_bump = 0;
         points_config.current_period = 1;
         points_config.period_total_points = 0;
         points_config.last_calculation_time = clock.unix_timestamp;  // Add this line
         points_config.bump = ctx.bumps.points_config;
 
+        let moderator = &mut ctx.accounts.moderator;
+        moderator.points_config = points_config.key();
+        moderator.authority = ctx.accounts.authority.key();
+        moderator.permissions = ALL_PERMISSIONS;
+        moderator.is_suspended = false;
+        moderator.added_at = clock.unix_timestamp;
+        moderator.bump = ctx.bumps.moderator;
+
         emit!(ProgramInitialized {
             authority: ctx.accounts.authority.key(),
             monthly_threshold: args.monthly_threshold,
@@ -75,6 +163,20 @@ impl Initialize<'_> {
             timestamp: clock.unix_timestamp,
         });
 
+        register_legacy_contribution_types(
+            points_config.key(),
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+        )?;
+
+        emit!(ModeratorAdded {
+            points_config: points_config.key(),
+            moderator: ctx.accounts.authority.key(),
+            permissions: ALL_PERMISSIONS,
+            timestamp: clock.unix_timestamp,
+        });
+
         Ok(())
     }
 }
@@ -86,10 +188,14 @@ impl CreateContributor<'_> {
         contributor.authority = ctx.accounts.authority.key();
         contributor.total_points = 0;
         contributor.current_month_points = 0;
+        contributor.points_period = 0;
         contributor.tokens_claimed = 0;
         contributor.last_claim_time = 0;
         contributor.contribution_count = 0;
         contributor.is_verified = false;
+        contributor.beneficiary = Pubkey::default();
+        contributor.carried_points = 0;
+        contributor.carried_from_period = 0;
         contributor.bump = ctx.bumps.contributor;
 
         emit!(ContributorCreated {
@@ -0,0 +1,445 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+use crate::state::{
+    contributor::Contributor,
+    contribution::Contribution,
+    contribution_type::ContributionTypeDef,
+    metadata_receipt::MetadataReceipt,
+    points::PointsConfig,
+};
+use crate::errors::RewardError;
+use crate::instructions::record_contribution::{
+    apply_monthly_cap,
+    load_period_points,
+    load_period_stats,
+    ContributionRecorded,
+};
+use crate::utils::write_account;
+
+// Layout of the single signature offsets entry in an Ed25519 program instruction
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_SIZE: usize = 14;
+const ED25519_PUBKEY_SIZE: usize = 32;
+
+#[derive(Accounts)]
+#[instruction(
+    type_id: u8,
+    metadata: [u8; 32],
+    impact_score: u8,
+    nonce: u64
+)]
+pub struct RecordAttestedContribution<'info> {
+    #[account(
+        mut,
+        seeds = [b"contributor", contributor.authority.as_ref()],
+        bump = contributor.bump,
+    )]
+    pub contributor: Account<'info, Contributor>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Contribution::SPACE,
+        seeds = [
+            b"contribution",
+            contributor.key().as_ref(),
+            &contributor.contribution_count.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(
+        seeds = [
+            b"contribution_type",
+            points_config.key().as_ref(),
+            &[type_id],
+        ],
+        bump = contribution_type.bump,
+        constraint = contribution_type.is_enabled @ RewardError::ContributionTypeDisabled,
+    )]
+    pub contribution_type: Account<'info, ContributionTypeDef>,
+
+    // `init` fails if the artifact was already recorded
+    #[account(
+        init,
+        payer = payer,
+        space = MetadataReceipt::SPACE,
+        seeds = [
+            b"metadata_receipt",
+            points_config.key().as_ref(),
+            &[type_id],
+            metadata.as_ref(),
+        ],
+        bump
+    )]
+    pub metadata_receipt: Account<'info, MetadataReceipt>,
+
+    /// CHECK: ContributorPeriodStats PDA, created on first use by load_period_stats
+    #[account(mut)]
+    pub period_stats: UncheckedAccount<'info>,
+
+    /// CHECK: ContributorPeriodPoints PDA for the current period, created on
+    /// first use by load_period_points; it also holds the attestation nonce
+    #[account(mut)]
+    pub period_points: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub points_config: Account<'info, PointsConfig>,
+
+    // Any relayer may pay the rent; the scoring agent only signs off-chain
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, used to inspect the Ed25519 verification instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RecordAttestedContribution<'info> {
+    pub fn process(
+        &mut self,
+        type_id: u8,
+        metadata: [u8; 32],
+        impact_score: u8,
+        nonce: u64,
+        bumps: &RecordAttestedContributionBumps,
+    ) -> Result<()> {
+        let agent = self.points_config.attestation_agent;
+
+        require!(
+            agent != Pubkey::default(),
+            RewardError::AttestationAgentNotSet
+        );
+
+        let period = self.points_config.current_period;
+        let points_info = self.period_points.to_account_info();
+        let mut period_points = load_period_points(
+            &self.payer.to_account_info(),
+            &points_info,
+            &self.system_program.to_account_info(),
+            self.points_config.key(),
+            self.contributor.key(),
+            period,
+        )?;
+
+        // Nonces must strictly increase per config, contributor and period;
+        // the signed message binds the same three
+        require!(
+            nonce > period_points.last_attestation_nonce,
+            RewardError::AttestationReplayed
+        );
+        period_points.last_attestation_nonce = nonce;
+
+        let message = attestation_message(
+            &self.points_config.key(),
+            period,
+            &self.contributor.key(),
+            type_id,
+            &metadata,
+            impact_score,
+            nonce,
+        );
+        verify_agent_signature(&self.instructions_sysvar, &agent, &message)?;
+
+        // Calculate points for this contribution
+        let points = self.points_config.calculate_contribution_points(
+            &self.contribution_type,
+            impact_score,
+        )?;
+
+        // Clamp the award to what is left of the monthly per-type cap
+        let stats_info = self.period_stats.to_account_info();
+        let mut period_stats = load_period_stats(
+            &self.payer.to_account_info(),
+            &stats_info,
+            &self.system_program.to_account_info(),
+            &self.points_config,
+            self.contributor.key(),
+            type_id,
+        )?;
+        let points = apply_monthly_cap(
+            &mut period_stats,
+            self.points_config.max_points_per_type,
+            points,
+        )?;
+        write_account(&stats_info, &period_stats)?;
+
+        // Initialize the contribution account
+        self.contribution.contributor = self.contributor.key();
+        self.contribution.points_config = self.points_config.key();
+        self.contribution.type_id = type_id;
+        self.contribution.points = points;
+        self.contribution.impact_score = impact_score;
+        self.contribution.timestamp = Clock::get()?.unix_timestamp;
+        self.contribution.metadata = metadata;
+        self.contribution.is_verified = !self.contribution_type.requires_verification;
+        self.contribution.period = period;
+        self.contribution.bump = bumps.contribution;
+
+        // Types that skip verification count toward the totals immediately
+        if self.contribution.is_verified {
+            period_points.credit(points)?;
+            self.contributor.credit_points(points, period)?;
+            self.points_config.update_period_points(points)?;
+        }
+        write_account(&points_info, &period_points)?;
+
+        // Claim the artifact so it cannot earn points twice
+        self.metadata_receipt.points_config = self.points_config.key();
+        self.metadata_receipt.contribution = self.contribution.key();
+        self.metadata_receipt.type_id = type_id;
+        self.metadata_receipt.metadata = metadata;
+        self.metadata_receipt.created_at = self.contribution.timestamp;
+        self.metadata_receipt.bump = bumps.metadata_receipt;
+
+        self.contributor.contribution_count = self.contributor.contribution_count
+            .checked_add(1)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        emit!(ContributionRecorded {
+            contributor: self.contributor.key(),
+            recorder: agent,
+            type_id,
+            points,
+            is_verified: self.contribution.is_verified,
+            timestamp: self.contribution.timestamp,
+            period: self.contribution.period,
+        });
+
+        Ok(())
+    }
+}
+
+// Message the scoring agent signs. The config and period are part of the
+// payload so an attestation cannot be replayed against another config or
+// in a later period.
+pub fn attestation_message(
+    points_config: &Pubkey,
+    period: u16,
+    contributor: &Pubkey,
+    type_id: u8,
+    metadata: &[u8; 32],
+    impact_score: u8,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(32 + 2 + 32 + 1 + 32 + 1 + 8);
+    message.extend_from_slice(points_config.as_ref());
+    message.extend_from_slice(&period.to_le_bytes());
+    message.extend_from_slice(contributor.as_ref());
+    message.push(type_id);
+    message.extend_from_slice(metadata);
+    message.push(impact_score);
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
+// Checks that the instruction right before this one is an Ed25519 program
+// instruction verifying `message` signed by `agent`
+fn verify_agent_signature(
+    instructions_sysvar: &AccountInfo,
+    agent: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, RewardError::MissingAttestation);
+
+    let ix = load_instruction_at_checked(
+        (current_index - 1) as usize,
+        instructions_sysvar,
+    )?;
+    require!(
+        ix.program_id == ed25519_program::ID && ix.accounts.is_empty(),
+        RewardError::MissingAttestation
+    );
+
+    check_ed25519_data(&ix.data, agent, message)
+}
+
+// Checks that Ed25519 program instruction data carries a single signature
+// by `agent` over `message`, with every offset pointing into the data itself
+fn check_ed25519_data(data: &[u8], agent: &Pubkey, message: &[u8]) -> Result<()> {
+    require!(
+        data.len() >= ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE && data[0] == 1,
+        RewardError::InvalidAttestation
+    );
+
+    let read_u16 = |offset: usize| -> usize {
+        let start = ED25519_OFFSETS_START + offset;
+        u16::from_le_bytes([data[start], data[start + 1]]) as usize
+    };
+    let signature_ix_index = read_u16(2);
+    let pubkey_offset = read_u16(4);
+    let pubkey_ix_index = read_u16(6);
+    let message_offset = read_u16(8);
+    let message_size = read_u16(10);
+    let message_ix_index = read_u16(12);
+
+    // All referenced data must live inside the Ed25519 instruction itself
+    let this_ix = u16::MAX as usize;
+    require!(
+        signature_ix_index == this_ix
+            && pubkey_ix_index == this_ix
+            && message_ix_index == this_ix,
+        RewardError::InvalidAttestation
+    );
+
+    let signed_pubkey = data
+        .get(pubkey_offset..pubkey_offset + ED25519_PUBKEY_SIZE)
+        .ok_or(RewardError::InvalidAttestation)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(RewardError::InvalidAttestation)?;
+
+    require!(
+        signed_pubkey == agent.as_ref() && signed_message == message,
+        RewardError::InvalidAttestation
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBKEY_OFFSET: usize = ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE;
+    const SIGNATURE_OFFSET: usize = PUBKEY_OFFSET + ED25519_PUBKEY_SIZE;
+    const MESSAGE_OFFSET: usize = SIGNATURE_OFFSET + 64;
+
+    struct Offsets {
+        signature_ix_index: u16,
+        pubkey_offset: u16,
+        pubkey_ix_index: u16,
+        message_offset: u16,
+        message_size: u16,
+        message_ix_index: u16,
+    }
+
+    impl Offsets {
+        fn valid(message: &[u8]) -> Self {
+            Offsets {
+                signature_ix_index: u16::MAX,
+                pubkey_offset: PUBKEY_OFFSET as u16,
+                pubkey_ix_index: u16::MAX,
+                message_offset: MESSAGE_OFFSET as u16,
+                message_size: message.len() as u16,
+                message_ix_index: u16::MAX,
+            }
+        }
+    }
+
+    // Same layout the Ed25519 program builds for a single signature
+    fn ed25519_data(signer: &Pubkey, message: &[u8], offsets: &Offsets) -> Vec<u8> {
+        let mut data = vec![1u8, 0];
+        for value in [
+            SIGNATURE_OFFSET as u16,
+            offsets.signature_ix_index,
+            offsets.pubkey_offset,
+            offsets.pubkey_ix_index,
+            offsets.message_offset,
+            offsets.message_size,
+            offsets.message_ix_index,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[7u8; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    fn sample_message() -> Vec<u8> {
+        attestation_message(&Pubkey::new_unique(), 3, &Pubkey::new_unique(), 1, &[9u8; 32], 4, 1)
+    }
+
+    #[test]
+    fn accepts_the_agent_signature_over_the_message() {
+        let agent = Pubkey::new_unique();
+        let message = sample_message();
+        let data = ed25519_data(&agent, &message, &Offsets::valid(&message));
+
+        assert!(check_ed25519_data(&data, &agent, &message).is_ok());
+    }
+
+    #[test]
+    fn rejects_another_signer() {
+        let agent = Pubkey::new_unique();
+        let message = sample_message();
+        let data = ed25519_data(&Pubkey::new_unique(), &message, &Offsets::valid(&message));
+
+        assert!(check_ed25519_data(&data, &agent, &message).is_err());
+    }
+
+    #[test]
+    fn rejects_another_message() {
+        let agent = Pubkey::new_unique();
+        let message = sample_message();
+        let data = ed25519_data(&agent, &message, &Offsets::valid(&message));
+
+        let mut tampered = message.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert!(check_ed25519_data(&data, &agent, &tampered).is_err());
+    }
+
+    #[test]
+    fn rejects_data_referenced_from_other_instructions() {
+        let agent = Pubkey::new_unique();
+        let message = sample_message();
+
+        for index in 0..3 {
+            let mut offsets = Offsets::valid(&message);
+            match index {
+                0 => offsets.signature_ix_index = 0,
+                1 => offsets.pubkey_ix_index = 0,
+                _ => offsets.message_ix_index = 0,
+            }
+            let data = ed25519_data(&agent, &message, &offsets);
+
+            assert!(check_ed25519_data(&data, &agent, &message).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_offsets_that_miss_the_signed_data() {
+        let agent = Pubkey::new_unique();
+        let message = sample_message();
+
+        // Pubkey offset pointing at the signature bytes
+        let mut offsets = Offsets::valid(&message);
+        offsets.pubkey_offset = SIGNATURE_OFFSET as u16;
+        let data = ed25519_data(&agent, &message, &offsets);
+        assert!(check_ed25519_data(&data, &agent, &message).is_err());
+
+        // Message running past the end of the data
+        let mut offsets = Offsets::valid(&message);
+        offsets.message_size += 1;
+        let data = ed25519_data(&agent, &message, &offsets);
+        assert!(check_ed25519_data(&data, &agent, &message).is_err());
+
+        // Message offset past the end of the data
+        let mut offsets = Offsets::valid(&message);
+        offsets.message_offset = u16::MAX - 1;
+        let data = ed25519_data(&agent, &message, &offsets);
+        assert!(check_ed25519_data(&data, &agent, &message).is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_or_multi_signature_data() {
+        let agent = Pubkey::new_unique();
+        let message = sample_message();
+        let data = ed25519_data(&agent, &message, &Offsets::valid(&message));
+
+        assert!(check_ed25519_data(&data[..ED25519_OFFSETS_START + 4], &agent, &message).is_err());
+
+        let mut two_signatures = data.clone();
+        two_signatures[0] = 2;
+        assert!(check_ed25519_data(&two_signatures, &agent, &message).is_err());
+    }
+}
@@ -1,19 +1,28 @@
 use anchor_lang::prelude::*;
 use crate::state::{
     contributor::Contributor,
-    contribution::{Contribution, ContributionType},
+    contribution::Contribution,
+    contribution_type::ContributionTypeDef,
+    metadata_receipt::MetadataReceipt,
+    period_points::ContributorPeriodPoints,
+    period_stats::ContributorPeriodStats,
     points::PointsConfig,
 };
 use crate::errors::RewardError;
+use crate::utils::{create_pda_account, write_account};
 
 #[derive(Accounts)]
 #[instruction(
-    contribution_type: ContributionType,
+    type_id: u8,
     metadata: [u8; 32],
     impact_score: u8
 )]
 pub struct RecordContribution<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"contributor", contributor.authority.as_ref()],
+        bump = contributor.bump,
+    )]
     pub contributor: Account<'info, Contributor>,
 
     #[account(
@@ -29,6 +38,40 @@ pub struct RecordContribution<'info> {
     )]
     pub contribution: Account<'info, Contribution>,
 
+    #[account(
+        seeds = [
+            b"contribution_type",
+            points_config.key().as_ref(),
+            &[type_id],
+        ],
+        bump = contribution_type.bump,
+        constraint = contribution_type.is_enabled @ RewardError::ContributionTypeDisabled,
+    )]
+    pub contribution_type: Account<'info, ContributionTypeDef>,
+
+    // `init` fails if the artifact was already recorded
+    #[account(
+        init,
+        payer = authority,
+        space = MetadataReceipt::SPACE,
+        seeds = [
+            b"metadata_receipt",
+            points_config.key().as_ref(),
+            &[type_id],
+            metadata.as_ref(),
+        ],
+        bump
+    )]
+    pub metadata_receipt: Account<'info, MetadataReceipt>,
+
+    /// CHECK: ContributorPeriodStats PDA, created on first use by load_period_stats
+    #[account(mut)]
+    pub period_stats: UncheckedAccount<'info>,
+
+    /// CHECK: ContributorPeriodPoints PDA, created on first credit by credit_period_points
+    #[account(mut)]
+    pub period_points: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub points_config: Account<'info, PointsConfig>,
 
@@ -41,50 +84,87 @@ pub struct RecordContribution<'info> {
 impl<'info> RecordContribution<'info> {
     pub fn process(
         &mut self,
-        contribution_type: ContributionType,
+        type_id: u8,
         metadata: [u8; 32],
         impact_score: u8,
-        bump: u8,
+        bumps: &RecordContributionBumps,
     ) -> Result<()> {
-        let contribution_type = contribution_type.clone(); // Clone here to avoid move
+        // Only the contributor (within the self-submission cap) or a trusted
+        // recorder may record contributions
+        let impact_score = self.points_config.authorize_recorder(
+            &self.authority.key(),
+            &self.contributor.authority,
+            impact_score,
+        )?;
 
         // Calculate points for this contribution
         let points = self.points_config.calculate_contribution_points(
-            &contribution_type,
+            &self.contribution_type,
             impact_score,
         )?;
 
+        // Clamp the award to what is left of the monthly per-type cap
+        let stats_info = self.period_stats.to_account_info();
+        let mut period_stats = load_period_stats(
+            &self.authority.to_account_info(),
+            &stats_info,
+            &self.system_program.to_account_info(),
+            &self.points_config,
+            self.contributor.key(),
+            type_id,
+        )?;
+        let points = apply_monthly_cap(
+            &mut period_stats,
+            self.points_config.max_points_per_type,
+            points,
+        )?;
+        write_account(&stats_info, &period_stats)?;
+
         // Initialize the contribution account
         self.contribution.contributor = self.contributor.key();
-        self.contribution.contribution_type = contribution_type.clone();
+        self.contribution.points_config = self.points_config.key();
+        self.contribution.type_id = type_id;
         self.contribution.points = points;
+        self.contribution.impact_score = impact_score;
         self.contribution.timestamp = Clock::get()?.unix_timestamp;
         self.contribution.metadata = metadata;
-        self.contribution.is_verified = false;
+        self.contribution.is_verified = !self.contribution_type.requires_verification;
         self.contribution.period = self.points_config.current_period;
-        self.contribution.bump = bump;
+        self.contribution.bump = bumps.contribution;
 
-        // Update contributor's points
-        self.contributor.total_points = self.contributor.total_points
-            .checked_add(points)
-            .ok_or(RewardError::InvalidPointsCalculation)?;
+        // Types that skip verification count toward the totals immediately
+        if self.contribution.is_verified {
+            credit_period_points(
+                &self.authority.to_account_info(),
+                &self.period_points.to_account_info(),
+                &self.system_program.to_account_info(),
+                self.points_config.key(),
+                &mut self.contributor,
+                self.contribution.period,
+                points,
+            )?;
+            self.points_config.update_period_points(points)?;
+        }
 
-        self.contributor.current_month_points = self.contributor.current_month_points
-            .checked_add(points)
-            .ok_or(RewardError::InvalidPointsCalculation)?;
+        // Claim the artifact so it cannot earn points twice
+        self.metadata_receipt.points_config = self.points_config.key();
+        self.metadata_receipt.contribution = self.contribution.key();
+        self.metadata_receipt.type_id = type_id;
+        self.metadata_receipt.metadata = metadata;
+        self.metadata_receipt.created_at = self.contribution.timestamp;
+        self.metadata_receipt.bump = bumps.metadata_receipt;
 
         self.contributor.contribution_count = self.contributor.contribution_count
             .checked_add(1)
             .ok_or(RewardError::InvalidPointsCalculation)?;
 
-        // Update total points in the current period
-        self.points_config.update_period_points(points)?;
-
         // Emit an event
         emit!(ContributionRecorded {
             contributor: self.contributor.key(),
-            contribution_type,
+            recorder: self.authority.key(),
+            type_id,
             points,
+            is_verified: self.contribution.is_verified,
             timestamp: self.contribution.timestamp,
             period: self.contribution.period,
         });
@@ -93,11 +173,160 @@ impl<'info> RecordContribution<'info> {
     }
 }
 
+// Loads the contributor's stats for the type in the current period,
+// creating the account on its first use
+pub fn load_period_stats<'info>(
+    payer: &AccountInfo<'info>,
+    stats_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    points_config: &Account<PointsConfig>,
+    contributor: Pubkey,
+    type_id: u8,
+) -> Result<ContributorPeriodStats> {
+    let points_config_key = points_config.key();
+    let period = points_config.current_period;
+    let period_bytes = period.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"period_stats",
+        points_config_key.as_ref(),
+        contributor.as_ref(),
+        &period_bytes,
+        &[type_id],
+    ];
+
+    if stats_info.data_is_empty() {
+        let bump = create_pda_account(
+            payer,
+            stats_info,
+            system_program,
+            ContributorPeriodStats::SPACE,
+            seeds,
+        )?;
+
+        return Ok(ContributorPeriodStats {
+            points_config: points_config_key,
+            contributor,
+            period,
+            type_id,
+            points: 0,
+            bump,
+        });
+    }
+
+    let (expected, _) = Pubkey::find_program_address(seeds, &crate::ID);
+    require_keys_eq!(stats_info.key(), expected, RewardError::InvalidAccountAddress);
+    require_keys_eq!(*stats_info.owner, crate::ID, RewardError::InvalidAccountAddress);
+
+    ContributorPeriodStats::try_deserialize(&mut &stats_info.try_borrow_data()?[..])
+}
+
+// Loads the contributor's claimable points for `period`, creating the
+// account on its first use
+pub fn load_period_points<'info>(
+    payer: &AccountInfo<'info>,
+    points_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    points_config: Pubkey,
+    contributor: Pubkey,
+    period: u16,
+) -> Result<ContributorPeriodPoints> {
+    let period_bytes = period.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"period_points",
+        points_config.as_ref(),
+        contributor.as_ref(),
+        &period_bytes,
+    ];
+
+    if points_info.data_is_empty() {
+        let bump = create_pda_account(
+            payer,
+            points_info,
+            system_program,
+            ContributorPeriodPoints::SPACE,
+            seeds,
+        )?;
+
+        return Ok(ContributorPeriodPoints {
+            points_config,
+            contributor,
+            period,
+            bump,
+            ..Default::default()
+        });
+    }
+
+    let (expected, _) = Pubkey::find_program_address(seeds, &crate::ID);
+    require_keys_eq!(points_info.key(), expected, RewardError::InvalidAccountAddress);
+    require_keys_eq!(*points_info.owner, crate::ID, RewardError::InvalidAccountAddress);
+
+    ContributorPeriodPoints::try_deserialize(&mut &points_info.try_borrow_data()?[..])
+}
+
+// Credits verified points to the contributor's lifetime totals and to what
+// they can claim for `period` under this config
+pub fn credit_period_points<'info>(
+    payer: &AccountInfo<'info>,
+    points_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    points_config: Pubkey,
+    contributor: &mut Account<Contributor>,
+    period: u16,
+    points: u64,
+) -> Result<()> {
+    let mut period_points = load_period_points(
+        payer,
+        points_info,
+        system_program,
+        points_config,
+        contributor.key(),
+        period,
+    )?;
+    period_points.credit(points)?;
+    write_account(points_info, &period_points)?;
+
+    contributor.credit_points(points, period)
+}
+
+// Clamps an award to what is left of the contributor's monthly cap for the type
+pub fn apply_monthly_cap(
+    period_stats: &mut ContributorPeriodStats,
+    cap: u64,
+    points: u64,
+) -> Result<u64> {
+    let awarded = period_stats.award(points, cap)?;
+
+    if period_stats.points >= cap {
+        emit!(MonthlyCapReached {
+            contributor: period_stats.contributor,
+            type_id: period_stats.type_id,
+            period: period_stats.period,
+            cap,
+            requested_points: points,
+            awarded_points: awarded,
+        });
+    }
+
+    Ok(awarded)
+}
+
 #[event]
 pub struct ContributionRecorded {
     pub contributor: Pubkey,
-    pub contribution_type: ContributionType,
+    pub recorder: Pubkey,
+    pub type_id: u8,
     pub points: u64,
+    pub is_verified: bool,
     pub timestamp: i64,
     pub period: u16,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct MonthlyCapReached {
+    pub contributor: Pubkey,
+    pub type_id: u8,
+    pub period: u16,
+    pub cap: u64,
+    pub requested_points: u64,
+    pub awarded_points: u64,
+}
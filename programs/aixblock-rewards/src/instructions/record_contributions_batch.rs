@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    contributor::Contributor,
+    contribution::Contribution,
+    contribution_type::ContributionTypeDef,
+    metadata_receipt::MetadataReceipt,
+    points::PointsConfig,
+};
+use crate::errors::RewardError;
+use crate::instructions::record_contribution::{
+    apply_monthly_cap,
+    credit_period_points,
+    load_period_stats,
+    ContributionRecorded,
+};
+use crate::utils::{create_pda_account, write_account};
+
+// Accounts each entry supplies through `remaining_accounts`, in order:
+// contribution, metadata receipt, contribution type, period stats
+pub const BATCH_ACCOUNTS_PER_ENTRY: usize = 4;
+pub const MAX_BATCH_CONTRIBUTIONS: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchContributionEntry {
+    pub type_id: u8,
+    pub metadata: [u8; 32],
+    pub impact_score: u8,
+}
+
+#[derive(Accounts)]
+pub struct RecordContributionsBatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"contributor", contributor.authority.as_ref()],
+        bump = contributor.bump,
+    )]
+    pub contributor: Account<'info, Contributor>,
+
+    #[account(mut)]
+    pub points_config: Account<'info, PointsConfig>,
+
+    /// CHECK: ContributorPeriodPoints PDA, created on first credit by credit_period_points
+    #[account(mut)]
+    pub period_points: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RecordContributionsBatch<'info> {
+    pub fn process(
+        &mut self,
+        entries: Vec<BatchContributionEntry>,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            !entries.is_empty()
+                && entries.len() <= MAX_BATCH_CONTRIBUTIONS
+                && remaining_accounts.len() == entries.len() * BATCH_ACCOUNTS_PER_ENTRY,
+            RewardError::InvalidBatch
+        );
+
+        let points_config_key = self.points_config.key();
+        let contributor_key = self.contributor.key();
+        let period = self.points_config.current_period;
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        let mut contribution_count = self.contributor.contribution_count;
+        let mut verified_points: u64 = 0;
+
+        for (entry, accounts) in entries.iter().zip(remaining_accounts.chunks(BATCH_ACCOUNTS_PER_ENTRY)) {
+            let (contribution_info, receipt_info, type_info, stats_info) =
+                (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+
+            // Only the contributor (within the self-submission cap) or a
+            // trusted recorder may record contributions
+            let impact_score = self.points_config.authorize_recorder(
+                &self.authority.key(),
+                &self.contributor.authority,
+                entry.impact_score,
+            )?;
+
+            let type_def = load_type_def(type_info, &points_config_key, entry.type_id)?;
+
+            // A receipt that already exists means this artifact was recorded
+            // before, possibly earlier in this batch
+            require!(receipt_info.data_is_empty(), RewardError::DuplicateContribution);
+
+            let points = self.points_config.calculate_contribution_points(
+                &type_def,
+                impact_score,
+            )?;
+
+            // Clamp the award to what is left of the monthly per-type cap
+            let mut period_stats = load_period_stats(
+                &self.authority.to_account_info(),
+                stats_info,
+                &self.system_program.to_account_info(),
+                &self.points_config,
+                contributor_key,
+                entry.type_id,
+            )?;
+            let points = apply_monthly_cap(
+                &mut period_stats,
+                self.points_config.max_points_per_type,
+                points,
+            )?;
+            write_account(stats_info, &period_stats)?;
+
+            let is_verified = !type_def.requires_verification;
+            if is_verified {
+                verified_points = verified_points
+                    .checked_add(points)
+                    .ok_or(RewardError::InvalidPointsCalculation)?;
+            }
+
+            let count_bytes = contribution_count.to_le_bytes();
+            let contribution_bump = create_pda_account(
+                &self.authority.to_account_info(),
+                contribution_info,
+                &self.system_program.to_account_info(),
+                Contribution::SPACE,
+                &[b"contribution", contributor_key.as_ref(), &count_bytes],
+            )?;
+
+            write_account(contribution_info, &Contribution {
+                contributor: contributor_key,
+                points_config: points_config_key,
+                type_id: entry.type_id,
+                points,
+                impact_score,
+                timestamp,
+                metadata: entry.metadata,
+                is_verified,
+                period,
+                bump: contribution_bump,
+            })?;
+
+            // Claim the artifact so it cannot earn points twice
+            let receipt_bump = create_pda_account(
+                &self.authority.to_account_info(),
+                receipt_info,
+                &self.system_program.to_account_info(),
+                MetadataReceipt::SPACE,
+                &[
+                    b"metadata_receipt",
+                    points_config_key.as_ref(),
+                    &[entry.type_id],
+                    entry.metadata.as_ref(),
+                ],
+            )?;
+
+            write_account(receipt_info, &MetadataReceipt {
+                points_config: points_config_key,
+                contribution: contribution_info.key(),
+                type_id: entry.type_id,
+                metadata: entry.metadata,
+                created_at: timestamp,
+                bump: receipt_bump,
+            })?;
+
+            contribution_count = contribution_count
+                .checked_add(1)
+                .ok_or(RewardError::InvalidPointsCalculation)?;
+
+            emit!(ContributionRecorded {
+                contributor: contributor_key,
+                recorder: self.authority.key(),
+                type_id: entry.type_id,
+                points,
+                is_verified,
+                timestamp,
+                period,
+            });
+        }
+
+        // Apply the batch totals once
+        self.contributor.contribution_count = contribution_count;
+
+        if verified_points > 0 {
+            credit_period_points(
+                &self.authority.to_account_info(),
+                &self.period_points.to_account_info(),
+                &self.system_program.to_account_info(),
+                points_config_key,
+                &mut self.contributor,
+                period,
+                verified_points,
+            )?;
+            self.points_config.update_period_points(verified_points)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Loads a ContributionTypeDef and checks it is the enabled definition for `type_id`
+fn load_type_def<'info>(
+    type_info: &'info AccountInfo<'info>,
+    points_config: &Pubkey,
+    type_id: u8,
+) -> Result<ContributionTypeDef> {
+    let type_def = Account::<ContributionTypeDef>::try_from(type_info)?;
+
+    let expected = Pubkey::create_program_address(
+        &[
+            b"contribution_type",
+            points_config.as_ref(),
+            &[type_id],
+            &[type_def.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| RewardError::InvalidAccountAddress)?;
+    require_keys_eq!(type_info.key(), expected, RewardError::InvalidAccountAddress);
+    require!(type_def.is_enabled, RewardError::ContributionTypeDisabled);
+
+    Ok(type_def.into_inner())
+}
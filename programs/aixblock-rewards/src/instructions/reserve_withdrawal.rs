@@ -0,0 +1,400 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{
+    points::PointsConfig,
+    reserve::{ReserveState, ReserveWithdrawal},
+};
+use crate::errors::RewardError;
+use crate::utils::transfer_checked_net;
+
+// Policy changes wait out the current withdrawal delay, so a pending
+// withdrawal cannot be sped up or unblocked by changing the policy
+#[derive(Accounts)]
+pub struct ProposeReserveWithdrawalPolicy<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+
+    #[account(
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    // Current guardian; must sign when the proposal replaces them
+    pub guardian: Option<Signer<'info>>,
+}
+
+// Permissionless once the policy timelock has passed
+#[derive(Accounts)]
+pub struct ApplyReserveWithdrawalPolicy<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+}
+
+// The config authority or the reserve guardian may cancel
+#[derive(Accounts)]
+pub struct CancelReserveWithdrawalPolicy<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump,
+        constraint = reserve_state.can_cancel_withdrawal(
+            &authority.key(),
+            &points_config.authority,
+        ) @ RewardError::Unauthorized,
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeReserveWithdrawal<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ReserveWithdrawal::SPACE,
+        seeds = [
+            b"reserve_withdrawal",
+            reserve_state.key().as_ref(),
+            &reserve_state.next_withdrawal_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub reserve_withdrawal: Account<'info, ReserveWithdrawal>,
+
+    #[account(address = points_config.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = destination.mint == reward_mint.key(),
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// The config authority or the reserve guardian may cancel
+#[derive(Accounts)]
+pub struct CancelReserveWithdrawal<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump,
+        constraint = reserve_state.can_cancel_withdrawal(
+            &authority.key(),
+            &points_config.authority,
+        ) @ RewardError::Unauthorized,
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"reserve_withdrawal",
+            reserve_state.key().as_ref(),
+            &reserve_withdrawal.id.to_le_bytes(),
+        ],
+        bump = reserve_withdrawal.bump
+    )]
+    pub reserve_withdrawal: Account<'info, ReserveWithdrawal>,
+
+    pub authority: Signer<'info>,
+}
+
+// Permissionless once the timelock has passed; the destination was fixed
+// when the withdrawal was proposed
+#[derive(Accounts)]
+pub struct ExecuteReserveWithdrawal<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"reserve_withdrawal",
+            reserve_state.key().as_ref(),
+            &reserve_withdrawal.id.to_le_bytes(),
+        ],
+        bump = reserve_withdrawal.bump
+    )]
+    pub reserve_withdrawal: Account<'info, ReserveWithdrawal>,
+
+    #[account(address = points_config.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = points_config.reserve_vault)]
+    pub reserve_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for reserve vault authority
+    #[account(
+        seeds = [b"reserve_authority", points_config.key().as_ref()],
+        bump = points_config.reserve_authority_bump
+    )]
+    pub reserve_vault_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = destination.key() == reserve_withdrawal.destination,
+        constraint = destination.mint == reward_mint.key(),
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ProposeReserveWithdrawalPolicy<'info> {
+    pub fn process(&mut self, guardian: Pubkey, delay: i64, limit: u64) -> Result<()> {
+        let current_guardian = self.reserve_state.guardian;
+        if current_guardian != Pubkey::default() && guardian != current_guardian {
+            require!(
+                self.guardian
+                    .as_ref()
+                    .is_some_and(|signer| signer.key() == current_guardian),
+                RewardError::GuardianApprovalRequired
+            );
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let effective_at = self.reserve_state.propose_policy(guardian, delay, limit, now)?;
+
+        emit!(ReserveWithdrawalPolicyProposed {
+            guardian,
+            delay,
+            limit,
+            effective_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ApplyReserveWithdrawalPolicy<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        self.reserve_state.apply_policy(now)?;
+
+        emit!(ReserveWithdrawalPolicyUpdated {
+            guardian: self.reserve_state.guardian,
+            delay: self.reserve_state.withdrawal_delay,
+            limit: self.reserve_state.withdrawal_limit,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> CancelReserveWithdrawalPolicy<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        require!(
+            self.reserve_state.has_pending_policy(),
+            RewardError::PolicyChangeNotPending
+        );
+
+        self.reserve_state.clear_pending_policy();
+
+        emit!(ReserveWithdrawalPolicyCancelled {
+            cancelled_by: self.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ProposeReserveWithdrawal<'info> {
+    pub fn process(&mut self, amount: u64, bump: u8) -> Result<()> {
+        require!(
+            amount <= self.reserve_state.withdrawal_limit,
+            RewardError::WithdrawalLimitExceeded
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let id = self.reserve_state.next_withdrawal_id;
+        let executable_at = now
+            .checked_add(self.reserve_state.withdrawal_delay)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        self.reserve_withdrawal.reserve_state = self.reserve_state.key();
+        self.reserve_withdrawal.id = id;
+        self.reserve_withdrawal.destination = self.destination.key();
+        self.reserve_withdrawal.amount = amount;
+        self.reserve_withdrawal.proposed_at = now;
+        self.reserve_withdrawal.executable_at = executable_at;
+        self.reserve_withdrawal.is_executed = false;
+        self.reserve_withdrawal.is_cancelled = false;
+        self.reserve_withdrawal.bump = bump;
+
+        self.reserve_state.next_withdrawal_id = id
+            .checked_add(1)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        emit!(ReserveWithdrawalProposed {
+            id,
+            destination: self.destination.key(),
+            amount,
+            executable_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> CancelReserveWithdrawal<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        require!(
+            self.reserve_withdrawal.is_pending(),
+            RewardError::WithdrawalNotPending
+        );
+
+        self.reserve_withdrawal.is_cancelled = true;
+
+        emit!(ReserveWithdrawalCancelled {
+            id: self.reserve_withdrawal.id,
+            cancelled_by: self.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ExecuteReserveWithdrawal<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let amount = self.reserve_withdrawal.amount;
+
+        require!(
+            self.reserve_withdrawal.is_pending(),
+            RewardError::WithdrawalNotPending
+        );
+        require!(
+            now >= self.reserve_withdrawal.executable_at,
+            RewardError::WithdrawalTimelocked
+        );
+
+        self.reserve_state.record_withdrawal(amount, self.points_config.current_period)?;
+        let (balance_before, balance_after) = self.reserve_state.record_outflow(amount)?;
+
+        let points_config_pubkey = self.points_config.key();
+        let seeds = &[
+            b"reserve_authority".as_ref(),
+            points_config_pubkey.as_ref(),
+            &[self.points_config.reserve_authority_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let received = transfer_checked_net(
+            &self.token_program,
+            &self.reserve_vault,
+            &mut self.destination,
+            &self.reward_mint,
+            &self.reserve_vault_authority,
+            amount,
+            signer_seeds,
+        )?;
+
+        self.reserve_withdrawal.is_executed = true;
+
+        emit!(ReserveWithdrawalExecuted {
+            id: self.reserve_withdrawal.id,
+            destination: self.destination.key(),
+            amount: received,
+            fee: amount - received,
+            balance_before,
+            balance_after,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct ReserveWithdrawalPolicyProposed {
+    pub guardian: Pubkey,
+    pub delay: i64,
+    pub limit: u64,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveWithdrawalPolicyCancelled {
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveWithdrawalPolicyUpdated {
+    pub guardian: Pubkey,
+    pub delay: i64,
+    pub limit: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveWithdrawalProposed {
+    pub id: u64,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveWithdrawalCancelled {
+    pub id: u64,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveWithdrawalExecuted {
+    pub id: u64,
+    pub destination: Pubkey,
+    // Net amount received after transfer fees
+    pub amount: u64,
+    pub fee: u64,
+    pub balance_before: u64,
+    pub balance_after: u64,
+    pub timestamp: i64,
+}
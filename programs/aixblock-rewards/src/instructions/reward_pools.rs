@@ -0,0 +1,629 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{
+    contributor::Contributor,
+    distribution::{ClaimReceipt, DistributionPeriod},
+    moderator::{Moderator, PERMISSION_CLOSE_PERIOD},
+    period_points::ContributorPeriodPoints,
+    points::PointsConfig,
+    reward_pool::{RewardPool, RewardPoolPeriod},
+};
+use crate::errors::RewardError;
+use crate::utils::{create_pda_account, transfer_checked_net, write_account};
+use crate::POOL_CLAIM_WINDOW;
+
+// Accounts each pool supplies to claim_all_pool_rewards through
+// `remaining_accounts`, in order: reward pool, pool period, claim receipt,
+// mint, pool vault, contributor token account
+pub const POOL_CLAIM_ACCOUNTS: usize = 6;
+
+#[derive(Accounts)]
+pub struct RegisterRewardPool<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = RewardPool::SPACE,
+        seeds = [
+            b"reward_pool",
+            points_config.key().as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    // Must already be owned by the pool PDA so only the program can pay out
+    #[account(
+        constraint = pool_vault.mint == mint.key(),
+        constraint = pool_vault.owner == reward_pool.key() @ RewardError::Unauthorized,
+    )]
+    pub pool_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRewardPool<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"reward_pool",
+            points_config.key().as_ref(),
+            reward_pool.mint.as_ref(),
+        ],
+        bump = reward_pool.bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(period: u16)]
+pub struct SnapshotRewardPool<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"reward_pool",
+            points_config.key().as_ref(),
+            reward_pool.mint.as_ref(),
+        ],
+        bump = reward_pool.bump,
+        constraint = reward_pool.is_active @ RewardError::RewardPoolInactive,
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(address = reward_pool.vault)]
+    pub pool_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // The closed period whose points the pool is split over
+    #[account(
+        seeds = [
+            b"distribution",
+            points_config.key().as_ref(),
+            &period.to_le_bytes(),
+        ],
+        bump = distribution_period.bump
+    )]
+    pub distribution_period: Account<'info, DistributionPeriod>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = RewardPoolPeriod::SPACE,
+        seeds = [
+            b"pool_period",
+            reward_pool.key().as_ref(),
+            &period.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pool_period: Account<'info, RewardPoolPeriod>,
+
+    #[account(
+        seeds = [
+            b"moderator",
+            points_config.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = moderator.bump,
+        constraint = !moderator.is_suspended @ RewardError::ModeratorSuspended,
+        constraint = moderator.has_permission(PERMISSION_CLOSE_PERIOD) @ RewardError::Unauthorized,
+    )]
+    pub moderator: Account<'info, Moderator>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPoolRewards<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"reward_pool",
+            points_config.key().as_ref(),
+            reward_pool.mint.as_ref(),
+        ],
+        bump = reward_pool.bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"pool_period",
+            reward_pool.key().as_ref(),
+            &pool_period.period.to_le_bytes(),
+        ],
+        bump = pool_period.bump
+    )]
+    pub pool_period: Account<'info, RewardPoolPeriod>,
+
+    // Exists once the contributor has claimed from this pool for the period
+    #[account(
+        init,
+        payer = payer,
+        space = ClaimReceipt::SPACE,
+        seeds = [
+            b"claim_receipt",
+            pool_period.key().as_ref(),
+            contributor.key().as_ref(),
+        ],
+        bump
+    )]
+    pub claim_receipt: Account<'info, ClaimReceipt>,
+
+    #[account(
+        seeds = [b"contributor", contributor.authority.as_ref()],
+        bump = contributor.bump,
+    )]
+    pub contributor: Account<'info, Contributor>,
+
+    // Points the contributor earned under this config in the period
+    #[account(
+        seeds = [
+            b"period_points",
+            points_config.key().as_ref(),
+            contributor.key().as_ref(),
+            &pool_period.period.to_le_bytes(),
+        ],
+        bump = period_points.bump
+    )]
+    pub period_points: Account<'info, ContributorPeriodPoints>,
+
+    #[account(address = reward_pool.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = reward_pool.vault)]
+    pub pool_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = contributor_token_account.mint == reward_pool.mint,
+        constraint = contributor.is_payout_destination(
+            &contributor_token_account.key(),
+            &reward_pool.mint,
+            &token_program.key(),
+        ) @ RewardError::InvalidPayoutDestination,
+    )]
+    pub contributor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Any cranker may trigger the claim and pay the rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Claims the contributor's share of `period` from every pool passed in
+// `remaining_accounts`, so one transaction can pay out all active pools
+#[derive(Accounts)]
+#[instruction(period: u16)]
+pub struct ClaimAllPoolRewards<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        seeds = [b"contributor", contributor.authority.as_ref()],
+        bump = contributor.bump,
+    )]
+    pub contributor: Account<'info, Contributor>,
+
+    // Points the contributor earned under this config in the period
+    #[account(
+        seeds = [
+            b"period_points",
+            points_config.key().as_ref(),
+            contributor.key().as_ref(),
+            &period.to_le_bytes(),
+        ],
+        bump = period_points.bump
+    )]
+    pub period_points: Account<'info, ContributorPeriodPoints>,
+
+    // Any cranker may trigger the claims and pay the rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Permissionless: frees the unclaimed part of an expired snapshot for later periods
+#[derive(Accounts)]
+pub struct ExpireRewardPoolPeriod<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"reward_pool",
+            reward_pool.points_config.as_ref(),
+            reward_pool.mint.as_ref(),
+        ],
+        bump = reward_pool.bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"pool_period",
+            reward_pool.key().as_ref(),
+            &pool_period.period.to_le_bytes(),
+        ],
+        bump = pool_period.bump
+    )]
+    pub pool_period: Account<'info, RewardPoolPeriod>,
+}
+
+impl<'info> RegisterRewardPool<'info> {
+    pub fn process(
+        &mut self,
+        pool_ratio_bps: u16,
+        max_per_period: u64,
+        bump: u8,
+    ) -> Result<()> {
+        self.reward_pool.points_config = self.points_config.key();
+        self.reward_pool.mint = self.mint.key();
+        self.reward_pool.vault = self.pool_vault.key();
+        self.reward_pool.is_active = true;
+        self.reward_pool.pool_ratio_bps = pool_ratio_bps;
+        self.reward_pool.max_per_period = max_per_period;
+        self.reward_pool.outstanding = 0;
+        self.reward_pool.bump = bump;
+
+        emit!(RewardPoolRegistered {
+            points_config: self.points_config.key(),
+            mint: self.mint.key(),
+            vault: self.pool_vault.key(),
+            pool_ratio_bps,
+            max_per_period,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> UpdateRewardPool<'info> {
+    pub fn process(
+        &mut self,
+        is_active: Option<bool>,
+        pool_ratio_bps: Option<u16>,
+        max_per_period: Option<u64>,
+    ) -> Result<()> {
+        if let Some(active) = is_active {
+            self.reward_pool.is_active = active;
+        }
+
+        if let Some(ratio) = pool_ratio_bps {
+            self.reward_pool.pool_ratio_bps = ratio;
+        }
+
+        if let Some(max) = max_per_period {
+            self.reward_pool.max_per_period = max;
+        }
+
+        emit!(RewardPoolUpdated {
+            mint: self.reward_pool.mint,
+            is_active: self.reward_pool.is_active,
+            pool_ratio_bps: self.reward_pool.pool_ratio_bps,
+            max_per_period: self.reward_pool.max_per_period,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> SnapshotRewardPool<'info> {
+    pub fn process(&mut self, period: u16, bump: u8) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            !self.distribution_period.is_expired(now),
+            RewardError::ClaimWindowExpired
+        );
+
+        let total_tokens = self.reward_pool.calculate_period_pool(self.pool_vault.amount)?;
+
+        self.reward_pool.outstanding = self.reward_pool.outstanding
+            .checked_add(total_tokens)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        self.pool_period.reward_pool = self.reward_pool.key();
+        self.pool_period.period = period;
+        self.pool_period.total_tokens = total_tokens;
+        self.pool_period.tokens_distributed = 0;
+        self.pool_period.total_points = self.distribution_period.total_points;
+        // Snapshots always expire so their unclaimed tokens are not held forever
+        self.pool_period.max_payout = if self.points_config.max_share_bps > 0 {
+            ((total_tokens as u128) * (self.points_config.max_share_bps as u128) / 10000) as u64
+        } else {
+            0
+        };
+        self.pool_period.excess_released = 0;
+        self.pool_period.claim_deadline = if self.distribution_period.claim_deadline != 0 {
+            self.distribution_period.claim_deadline
+        } else {
+            now.checked_add(POOL_CLAIM_WINDOW)
+                .ok_or(RewardError::InvalidPointsCalculation)?
+        };
+        self.pool_period.is_completed = false;
+        self.pool_period.bump = bump;
+
+        emit!(RewardPoolSnapshot {
+            mint: self.reward_pool.mint,
+            period,
+            total_tokens,
+            total_points: self.pool_period.total_points,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ClaimPoolRewards<'info> {
+    pub fn process(&mut self, receipt_bump: u8) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let period = self.pool_period.period;
+
+        let points = self.period_points.claimable();
+        let (amount, excess) = self.pool_period.claim(points, now)?;
+
+        let points_config_pubkey = self.points_config.key();
+        let mint_pubkey = self.reward_pool.mint;
+        let seeds = &[
+            b"reward_pool".as_ref(),
+            points_config_pubkey.as_ref(),
+            mint_pubkey.as_ref(),
+            &[self.reward_pool.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let received = transfer_checked_net(
+            &self.token_program,
+            &self.pool_vault,
+            &mut self.contributor_token_account,
+            &self.mint,
+            &self.reward_pool.to_account_info(),
+            amount,
+            signer_seeds,
+        )?;
+
+        // The capped excess is freed for later snapshots
+        self.reward_pool.outstanding = self.reward_pool.outstanding
+            .saturating_sub(amount)
+            .saturating_sub(excess);
+
+        self.claim_receipt.distribution_period = self.pool_period.key();
+        self.claim_receipt.contributor = self.contributor.key();
+        self.claim_receipt.points = points;
+        self.claim_receipt.amount = received;
+        self.claim_receipt.claimed_at = now;
+        self.claim_receipt.bump = receipt_bump;
+
+        emit!(PoolRewardsClaimed {
+            contributor: self.contributor.key(),
+            recipient: self.contributor_token_account.key(),
+            mint: mint_pubkey,
+            period,
+            points,
+            amount: received,
+            fee: amount - received,
+            excess,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ClaimAllPoolRewards<'info> {
+    pub fn process(
+        &mut self,
+        period: u16,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            !remaining_accounts.is_empty()
+                && remaining_accounts.chunks_exact(POOL_CLAIM_ACCOUNTS).remainder().is_empty(),
+            RewardError::InvalidBatch
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let points_config_key = self.points_config.key();
+        let contributor_key = self.contributor.key();
+        let points = self.period_points.claimable();
+
+        for accounts in remaining_accounts.chunks(POOL_CLAIM_ACCOUNTS) {
+            let (pool_info, pool_period_info, receipt_info) =
+                (&accounts[0], &accounts[1], &accounts[2]);
+
+            // Program-owned pools and snapshots are only ever created at
+            // their PDAs, so the stored links identify them
+            let mut reward_pool = Account::<RewardPool>::try_from(pool_info)?;
+            require_keys_eq!(
+                reward_pool.points_config,
+                points_config_key,
+                RewardError::ConfigMismatch
+            );
+
+            let mut pool_period = Account::<RewardPoolPeriod>::try_from(pool_period_info)?;
+            require!(
+                pool_period.reward_pool == reward_pool.key() && pool_period.period == period,
+                RewardError::ConfigMismatch
+            );
+
+            let mint = InterfaceAccount::<Mint>::try_from(&accounts[3])?;
+            let pool_vault = InterfaceAccount::<TokenAccount>::try_from(&accounts[4])?;
+            let mut destination = InterfaceAccount::<TokenAccount>::try_from(&accounts[5])?;
+            require_keys_eq!(mint.key(), reward_pool.mint, RewardError::InvalidAccountAddress);
+            require_keys_eq!(pool_vault.key(), reward_pool.vault, RewardError::InvalidAccountAddress);
+            require!(
+                destination.mint == reward_pool.mint
+                    && self.contributor.is_payout_destination(
+                        &destination.key(),
+                        &reward_pool.mint,
+                        &self.token_program.key(),
+                    ),
+                RewardError::InvalidPayoutDestination
+            );
+
+            // Creating the receipt fails if the contributor already claimed
+            let pool_period_key = pool_period.key();
+            let receipt_bump = create_pda_account(
+                &self.payer.to_account_info(),
+                receipt_info,
+                &self.system_program.to_account_info(),
+                ClaimReceipt::SPACE,
+                &[b"claim_receipt", pool_period_key.as_ref(), contributor_key.as_ref()],
+            )?;
+
+            let (amount, excess) = pool_period.claim(points, now)?;
+
+            let mint_key = reward_pool.mint;
+            let seeds = &[
+                b"reward_pool".as_ref(),
+                points_config_key.as_ref(),
+                mint_key.as_ref(),
+                &[reward_pool.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let received = transfer_checked_net(
+                &self.token_program,
+                &pool_vault,
+                &mut destination,
+                &mint,
+                pool_info,
+                amount,
+                signer_seeds,
+            )?;
+
+            reward_pool.outstanding = reward_pool.outstanding
+                .saturating_sub(amount)
+                .saturating_sub(excess);
+            reward_pool.exit(&crate::ID)?;
+            pool_period.exit(&crate::ID)?;
+
+            write_account(receipt_info, &ClaimReceipt {
+                distribution_period: pool_period_key,
+                contributor: contributor_key,
+                points,
+                amount: received,
+                claimed_at: now,
+                bump: receipt_bump,
+            })?;
+
+            emit!(PoolRewardsClaimed {
+                contributor: contributor_key,
+                recipient: destination.key(),
+                mint: mint_key,
+                period,
+                points,
+                amount: received,
+                fee: amount - received,
+                excess,
+                timestamp: now,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<'info> ExpireRewardPoolPeriod<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(!self.pool_period.is_completed, RewardError::DistributionAlreadyProcessed);
+        require!(self.pool_period.is_expired(now), RewardError::ClaimWindowOpen);
+
+        let released = self.pool_period.unsettled_tokens();
+
+        self.reward_pool.outstanding = self.reward_pool.outstanding.saturating_sub(released);
+        self.pool_period.is_completed = true;
+
+        emit!(RewardPoolPeriodExpired {
+            mint: self.reward_pool.mint,
+            period: self.pool_period.period,
+            released,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct RewardPoolRegistered {
+    pub points_config: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub pool_ratio_bps: u16,
+    pub max_per_period: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardPoolUpdated {
+    pub mint: Pubkey,
+    pub is_active: bool,
+    pub pool_ratio_bps: u16,
+    pub max_per_period: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardPoolSnapshot {
+    pub mint: Pubkey,
+    pub period: u16,
+    pub total_tokens: u64,
+    pub total_points: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolRewardsClaimed {
+    pub contributor: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub period: u16,
+    pub points: u64,
+    // Net amount received after transfer fees
+    pub amount: u64,
+    pub fee: u64,
+    // Capped excess released back to the pool
+    pub excess: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardPoolPeriodExpired {
+    pub mint: Pubkey,
+    pub period: u16,
+    pub released: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{
+    distribution::DistributionPeriod,
+    points::PointsConfig,
+    reserve::ReserveState,
+};
+use crate::errors::RewardError;
+use crate::utils::transfer_checked_net;
+
+#[derive(Accounts)]
+pub struct UpdateClaimWindow<'info> {
+    #[account(mut)]
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+// Permissionless: anyone may crank an expired period back into the reserve
+#[derive(Accounts)]
+pub struct SweepExpiredPeriod<'info> {
+    #[account(mut)]
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"distribution",
+            points_config.key().as_ref(),
+            &distribution_period.period.to_le_bytes(),
+        ],
+        bump = distribution_period.bump
+    )]
+    pub distribution_period: Account<'info, DistributionPeriod>,
+
+    #[account(address = points_config.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = points_config.reward_vault)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for reward vault authority
+    #[account(
+        seeds = [b"vault_authority", points_config.key().as_ref()],
+        bump = points_config.vault_authority_bump
+    )]
+    pub reward_vault_authority: AccountInfo<'info>,
+
+    #[account(mut, address = points_config.reserve_vault)]
+    pub reserve_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for reserve vault authority
+    #[account(
+        seeds = [b"reserve_authority", points_config.key().as_ref()],
+        bump = points_config.reserve_authority_bump
+    )]
+    pub reserve_vault_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve_state", points_config.key().as_ref()],
+        bump = reserve_state.bump
+    )]
+    pub reserve_state: Account<'info, ReserveState>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> UpdateClaimWindow<'info> {
+    pub fn process(&mut self, claim_window: i64) -> Result<()> {
+        self.points_config.claim_window = claim_window;
+
+        emit!(ClaimWindowUpdated {
+            points_config: self.points_config.key(),
+            claim_window,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> SweepExpiredPeriod<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            !self.distribution_period.is_completed,
+            RewardError::DistributionAlreadyProcessed
+        );
+        require!(
+            self.distribution_period.is_expired(now),
+            RewardError::ClaimWindowOpen
+        );
+
+        let unclaimed = self.distribution_period.unsettled_tokens();
+
+        let mut balance_before = self.reserve_state.balance;
+        let mut balance_after = balance_before;
+        let mut received = 0;
+
+        if unclaimed > 0 {
+            let points_config_pubkey = self.points_config.key();
+            let seeds = &[
+                b"vault_authority".as_ref(),
+                points_config_pubkey.as_ref(),
+                &[self.points_config.vault_authority_bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            received = transfer_checked_net(
+                &self.token_program,
+                &self.reward_vault,
+                &mut self.reserve_vault,
+                &self.reward_mint,
+                &self.reward_vault_authority,
+                unclaimed,
+                signer_seeds,
+            )?;
+
+            (balance_before, balance_after) = self.reserve_state.record_inflow(
+                received,
+                self.distribution_period.period,
+            )?;
+        }
+
+        self.points_config.release_committed_rewards(unclaimed);
+        self.distribution_period.unclaimed_swept = unclaimed;
+        self.distribution_period.is_completed = true;
+
+        emit!(ExpiredPeriodSwept {
+            period: self.distribution_period.period,
+            amount: received,
+            fee: unclaimed - received,
+            tokens_distributed: self.distribution_period.tokens_distributed,
+            balance_before,
+            balance_after,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct ClaimWindowUpdated {
+    pub points_config: Pubkey,
+    pub claim_window: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExpiredPeriodSwept {
+    pub period: u16,
+    // Net amount received after transfer fees
+    pub amount: u64,
+    pub fee: u64,
+    pub tokens_distributed: u64,
+    pub balance_before: u64,
+    pub balance_after: u64,
+    pub timestamp: i64,
+}
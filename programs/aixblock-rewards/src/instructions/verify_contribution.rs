@@ -0,0 +1,295 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    contributor::Contributor,
+    contribution::Contribution,
+    contribution_type::ContributionTypeDef,
+    metadata_receipt::MetadataReceipt,
+    period_stats::ContributorPeriodStats,
+    moderator::{Moderator, PERMISSION_ADJUST_IMPACT, PERMISSION_VERIFY_CONTRIBUTIONS},
+    points::PointsConfig,
+};
+use crate::errors::RewardError;
+use crate::instructions::record_contribution::{apply_monthly_cap, credit_period_points};
+
+#[derive(Accounts)]
+pub struct VerifyContribution<'info> {
+    #[account(mut)]
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        constraint = contribution.points_config == points_config.key() @ RewardError::ConfigMismatch,
+        constraint = contribution.contributor == contributor.key() @ RewardError::ContributorNotFound,
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(mut)]
+    pub contributor: Account<'info, Contributor>,
+
+    #[account(
+        seeds = [
+            b"moderator",
+            points_config.key().as_ref(),
+            reviewer.key().as_ref(),
+        ],
+        bump = moderator.bump,
+        constraint = !moderator.is_suspended @ RewardError::ModeratorSuspended,
+        constraint = moderator.has_permission(PERMISSION_VERIFY_CONTRIBUTIONS) @ RewardError::Unauthorized,
+    )]
+    pub moderator: Account<'info, Moderator>,
+
+    /// CHECK: ContributorPeriodPoints PDA, created on first credit by credit_period_points
+    #[account(mut)]
+    pub period_points: UncheckedAccount<'info>,
+
+    // Pays for the period points account on the contributor's first credit
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RejectContribution<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        close = reviewer,
+        constraint = contribution.points_config == points_config.key() @ RewardError::ConfigMismatch,
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    // Released receipts let the artifact be recorded again after rejection
+    #[account(
+        mut,
+        seeds = [
+            b"metadata_receipt",
+            points_config.key().as_ref(),
+            &[contribution.type_id],
+            contribution.metadata.as_ref(),
+        ],
+        bump = metadata_receipt.bump,
+        constraint = metadata_receipt.contribution == contribution.key() @ RewardError::ConfigMismatch,
+    )]
+    pub metadata_receipt: Account<'info, MetadataReceipt>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"period_stats",
+            points_config.key().as_ref(),
+            contribution.contributor.as_ref(),
+            &contribution.period.to_le_bytes(),
+            &[contribution.type_id],
+        ],
+        bump = period_stats.bump,
+    )]
+    pub period_stats: Account<'info, ContributorPeriodStats>,
+
+    #[account(
+        seeds = [
+            b"moderator",
+            points_config.key().as_ref(),
+            reviewer.key().as_ref(),
+        ],
+        bump = moderator.bump,
+        constraint = !moderator.is_suspended @ RewardError::ModeratorSuspended,
+        constraint = moderator.has_permission(PERMISSION_VERIFY_CONTRIBUTIONS) @ RewardError::Unauthorized,
+    )]
+    pub moderator: Account<'info, Moderator>,
+
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdjustContributionImpact<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        mut,
+        constraint = contribution.points_config == points_config.key() @ RewardError::ConfigMismatch,
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"period_stats",
+            points_config.key().as_ref(),
+            contribution.contributor.as_ref(),
+            &contribution.period.to_le_bytes(),
+            &[contribution.type_id],
+        ],
+        bump = period_stats.bump,
+    )]
+    pub period_stats: Account<'info, ContributorPeriodStats>,
+
+    #[account(
+        seeds = [
+            b"contribution_type",
+            points_config.key().as_ref(),
+            &[contribution.type_id],
+        ],
+        bump = contribution_type.bump,
+    )]
+    pub contribution_type: Account<'info, ContributionTypeDef>,
+
+    #[account(
+        seeds = [
+            b"moderator",
+            points_config.key().as_ref(),
+            reviewer.key().as_ref(),
+        ],
+        bump = moderator.bump,
+        constraint = !moderator.is_suspended @ RewardError::ModeratorSuspended,
+        constraint = moderator.has_permission(PERMISSION_ADJUST_IMPACT) @ RewardError::Unauthorized,
+    )]
+    pub moderator: Account<'info, Moderator>,
+
+    pub reviewer: Signer<'info>,
+}
+
+impl<'info> VerifyContribution<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        require!(
+            !self.contribution.is_verified,
+            RewardError::ContributionAlreadyVerified
+        );
+
+        // A closed period's totals are final, so contributions still pending
+        // when their period closed are credited to the current period
+        let period = self.points_config.current_period;
+        let points = self.contribution.points;
+        self.contribution.is_verified = true;
+
+        // Update contributor's points
+        credit_period_points(
+            &self.reviewer.to_account_info(),
+            &self.period_points.to_account_info(),
+            &self.system_program.to_account_info(),
+            self.points_config.key(),
+            &mut self.contributor,
+            period,
+            points,
+        )?;
+
+        // Update total points in the current period
+        self.points_config.update_period_points(points)?;
+
+        emit!(ContributionVerified {
+            contributor: self.contributor.key(),
+            contribution: self.contribution.key(),
+            reviewer: self.reviewer.key(),
+            points,
+            period,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> RejectContribution<'info> {
+    pub fn process(&mut self, release_receipt: bool) -> Result<()> {
+        // Verified points are already part of the period totals
+        require!(
+            !self.contribution.is_verified,
+            RewardError::ContributionAlreadyVerified
+        );
+
+        // Rejected points no longer count toward the monthly cap
+        self.period_stats.release(self.contribution.points);
+
+        // Keeping the receipt blocks the artifact from being recorded again
+        if release_receipt {
+            self.metadata_receipt.close(self.reviewer.to_account_info())?;
+        }
+
+        emit!(ContributionRejected {
+            contributor: self.contribution.contributor,
+            contribution: self.contribution.key(),
+            reviewer: self.reviewer.key(),
+            points: self.contribution.points,
+            period: self.contribution.period,
+            receipt_released: release_receipt,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> AdjustContributionImpact<'info> {
+    pub fn process(&mut self, impact_score: u8) -> Result<()> {
+        // Verified points are already part of the period totals
+        require!(
+            !self.contribution.is_verified,
+            RewardError::ContributionAlreadyVerified
+        );
+
+        let old_points = self.contribution.points;
+        let old_impact_score = self.contribution.impact_score;
+
+        let points = self.points_config.calculate_contribution_points(
+            &self.contribution_type,
+            impact_score,
+        )?;
+
+        // Re-apply the monthly cap with the old award released
+        self.period_stats.release(old_points);
+        let points = apply_monthly_cap(
+            &mut self.period_stats,
+            self.points_config.max_points_per_type,
+            points,
+        )?;
+
+        self.contribution.points = points;
+        self.contribution.impact_score = impact_score;
+
+        emit!(ContributionImpactAdjusted {
+            contribution: self.contribution.key(),
+            reviewer: self.reviewer.key(),
+            old_impact_score,
+            new_impact_score: impact_score,
+            old_points,
+            new_points: points,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct ContributionVerified {
+    pub contributor: Pubkey,
+    pub contribution: Pubkey,
+    pub reviewer: Pubkey,
+    pub points: u64,
+    pub period: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ContributionRejected {
+    pub contributor: Pubkey,
+    pub contribution: Pubkey,
+    pub reviewer: Pubkey,
+    pub points: u64,
+    pub period: u16,
+    pub receipt_released: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ContributionImpactAdjusted {
+    pub contribution: Pubkey,
+    pub reviewer: Pubkey,
+    pub old_impact_score: u8,
+    pub new_impact_score: u8,
+    pub old_points: u64,
+    pub new_points: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{
+    contributor::Contributor,
+    points::PointsConfig,
+    vesting::VestingEscrow,
+};
+use crate::errors::RewardError;
+use crate::utils::transfer_checked_net;
+
+#[derive(Accounts)]
+pub struct UpdateVestingPolicy<'info> {
+    #[account(mut)]
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        constraint = points_config.authority == authority.key() @ RewardError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+// Permissionless: unlocked tokens can only go to the contributor's payout accounts
+#[derive(Accounts)]
+pub struct WithdrawVested<'info> {
+    pub points_config: Account<'info, PointsConfig>,
+
+    #[account(
+        seeds = [b"contributor", contributor.authority.as_ref()],
+        bump = contributor.bump,
+    )]
+    pub contributor: Account<'info, Contributor>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vesting_escrow",
+            points_config.key().as_ref(),
+            contributor.key().as_ref(),
+            &vesting_escrow.period.to_le_bytes(),
+        ],
+        bump = vesting_escrow.bump,
+    )]
+    pub vesting_escrow: Account<'info, VestingEscrow>,
+
+    #[account(address = points_config.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = points_config.vesting_vault)]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for vesting vault authority
+    #[account(
+        seeds = [b"vesting_authority", points_config.key().as_ref()],
+        bump = points_config.vesting_authority_bump
+    )]
+    pub vesting_vault_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = contributor_token_account.mint == vesting_vault.mint,
+        constraint = contributor.is_payout_destination(
+            &contributor_token_account.key(),
+            &reward_mint.key(),
+            &token_program.key(),
+        ) @ RewardError::InvalidPayoutDestination,
+    )]
+    pub contributor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> UpdateVestingPolicy<'info> {
+    pub fn process(
+        &mut self,
+        cliff: i64,
+        duration: i64,
+        immediate_bps: u16,
+    ) -> Result<()> {
+        self.points_config.vesting_cliff = cliff;
+        self.points_config.vesting_duration = duration;
+        self.points_config.vesting_immediate_bps = immediate_bps;
+
+        emit!(VestingPolicyUpdated {
+            points_config: self.points_config.key(),
+            cliff,
+            duration,
+            immediate_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> WithdrawVested<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        let vested = self.vesting_escrow.vested_amount(now)?;
+        let amount = vested.saturating_sub(self.vesting_escrow.released_amount);
+        require!(amount > 0, RewardError::NothingToClaim);
+
+        let points_config_pubkey = self.points_config.key();
+        let seeds = &[
+            b"vesting_authority".as_ref(),
+            points_config_pubkey.as_ref(),
+            &[self.points_config.vesting_authority_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let received = transfer_checked_net(
+            &self.token_program,
+            &self.vesting_vault,
+            &mut self.contributor_token_account,
+            &self.reward_mint,
+            &self.vesting_vault_authority,
+            amount,
+            signer_seeds,
+        )?;
+
+        self.vesting_escrow.released_amount = vested;
+
+        emit!(VestedTokensWithdrawn {
+            contributor: self.contributor.key(),
+            period: self.vesting_escrow.period,
+            amount: received,
+            fee: amount - received,
+            released: self.vesting_escrow.released_amount,
+            locked: self.vesting_escrow.locked_amount(),
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct VestingPolicyUpdated {
+    pub points_config: Pubkey,
+    pub cliff: i64,
+    pub duration: i64,
+    pub immediate_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestedTokensWithdrawn {
+    pub contributor: Pubkey,
+    pub period: u16,
+    // Net amount received after transfer fees
+    pub amount: u64,
+    pub fee: u64,
+    pub released: u64,
+    pub locked: u64,
+    pub timestamp: i64,
+}
@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
 use crate::instructions::*;
-use crate::state::ContributionType;
+use crate::state::{LEGACY_CONTRIBUTION_TYPE_COUNT, MAX_TYPE_NAME_LEN};
 
 pub mod errors;
 pub mod instructions;
 pub mod state;
+pub mod utils;
 
 declare_id!("BV7MhRzrPUKPjBFYHJkuipQTcKjkSLAFJzsF3zNUYeB6");
 
@@ -12,39 +13,210 @@ declare_id!("BV7MhRzrPUKPjBFYHJkuipQTcKjkSLAFJzsF3zNUYeB6");
 pub mod aixblock_rewards {
     use super::*;
 
-    pub fn initialize(
-        ctx: Context<Initialize>,
+    pub fn initialize<'info>(
+        ctx: Context<'_, '_, '_, 'info, Initialize<'info>>,
         args: InitializeArgs,
     ) -> Result<()> {
+        validate_self_submission_cap(args.self_submission_max_impact)?;
         Initialize::process(ctx, args)
     }
 
+    pub fn initialize_vaults(
+        ctx: Context<InitializeVaults>,
+    ) -> Result<()> {
+        ctx.accounts.process(&ctx.bumps)
+    }
+
+    pub fn adopt_vaults(
+        ctx: Context<AdoptVaults>,
+    ) -> Result<()> {
+        ctx.accounts.process(&ctx.bumps)
+    }
+
+    pub fn migrate_points_config<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigratePointsConfig<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    pub fn migrate_contributor(
+        ctx: Context<MigrateContributor>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn migrate_contribution(
+        ctx: Context<MigrateContribution>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
     pub fn create_contributor(
         ctx: Context<CreateContributor>,
     ) -> Result<()> {
         CreateContributor::process(ctx)
     }
 
+    pub fn set_beneficiary(
+        ctx: Context<SetBeneficiary>,
+        beneficiary: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.process(beneficiary)
+    }
+
     pub fn record_contribution(
         ctx: Context<RecordContribution>,
-        contribution_type: ContributionType,
+        type_id: u8,
+        metadata: [u8; 32],
+        impact_score: u8,
+    ) -> Result<()> {
+        validate_impact_score(impact_score)?;
+        ctx.accounts.process(
+            type_id,
+            metadata,
+            impact_score,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn verify_contribution(
+        ctx: Context<VerifyContribution>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn reject_contribution(
+        ctx: Context<RejectContribution>,
+        release_receipt: bool,
+    ) -> Result<()> {
+        ctx.accounts.process(release_receipt)
+    }
+
+    pub fn adjust_contribution_impact(
+        ctx: Context<AdjustContributionImpact>,
+        impact_score: u8,
+    ) -> Result<()> {
+        validate_impact_score(impact_score)?;
+        ctx.accounts.process(impact_score)
+    }
+
+    pub fn add_moderator(
+        ctx: Context<AddModerator>,
+        moderator_key: Pubkey,
+        permissions: u8,
+    ) -> Result<()> {
+        let bump = ctx.bumps.moderator;
+        ctx.accounts.process(moderator_key, permissions, bump)
+    }
+
+    pub fn remove_moderator(
+        ctx: Context<RemoveModerator>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn suspend_moderator(
+        ctx: Context<SuspendModerator>,
+        suspended: bool,
+    ) -> Result<()> {
+        ctx.accounts.process(suspended)
+    }
+
+    pub fn add_recorder(
+        ctx: Context<ManageRecorders>,
+        recorder: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.add_recorder(recorder)
+    }
+
+    pub fn remove_recorder(
+        ctx: Context<ManageRecorders>,
+        recorder: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.remove_recorder(recorder)
+    }
+
+    pub fn update_recorder_policy(
+        ctx: Context<ManageRecorders>,
+        self_submission_max_impact: u8,
+    ) -> Result<()> {
+        validate_self_submission_cap(self_submission_max_impact)?;
+        ctx.accounts.update_recorder_policy(self_submission_max_impact)
+    }
+
+    pub fn update_attestation_agent(
+        ctx: Context<ManageRecorders>,
+        agent: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.update_attestation_agent(agent)
+    }
+
+    pub fn record_attested_contribution(
+        ctx: Context<RecordAttestedContribution>,
+        type_id: u8,
         metadata: [u8; 32],
         impact_score: u8,
-        bump: u8,
+        nonce: u64,
     ) -> Result<()> {
         validate_impact_score(impact_score)?;
         ctx.accounts.process(
-            contribution_type,
+            type_id,
             metadata,
             impact_score,
-            bump,
+            nonce,
+            &ctx.bumps,
         )
     }
 
+    pub fn register_contribution_type(
+        ctx: Context<RegisterContributionType>,
+        type_id: u8,
+        name: String,
+        base_points: u64,
+        requires_verification: bool,
+    ) -> Result<()> {
+        validate_type_name(&name)?;
+        validate_points(base_points)?;
+        let bump = ctx.bumps.contribution_type;
+        ctx.accounts.process(type_id, name, base_points, requires_verification, bump)
+    }
+
+    pub fn update_base_points<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateBasePoints<'info>>,
+        new_base_points: [u64; LEGACY_CONTRIBUTION_TYPE_COUNT],
+    ) -> Result<()> {
+        for points in new_base_points {
+            validate_points(points)?;
+        }
+        ctx.accounts.process(new_base_points, ctx.remaining_accounts)
+    }
+
+    pub fn update_contribution_type(
+        ctx: Context<UpdateContributionType>,
+        new_base_points: Option<u64>,
+        is_enabled: Option<bool>,
+        requires_verification: Option<bool>,
+    ) -> Result<()> {
+        if let Some(points) = new_base_points {
+            validate_points(points)?;
+        }
+        ctx.accounts.process(new_base_points, is_enabled, requires_verification)
+    }
+
+    pub fn record_contributions_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecordContributionsBatch<'info>>,
+        entries: Vec<BatchContributionEntry>,
+    ) -> Result<()> {
+        for entry in entries.iter() {
+            validate_impact_score(entry.impact_score)?;
+        }
+        ctx.accounts.process(entries, ctx.remaining_accounts)
+    }
+
     pub fn calculate_monthly_points(
         ctx: Context<CalculateMonthlyPoints>,
     ) -> Result<()> {
-        ctx.accounts.process()
+        ctx.accounts.process(&ctx.bumps)
     }
 
     pub fn update_contributor_points(
@@ -53,29 +225,169 @@ pub mod aixblock_rewards {
         ctx.accounts.process()
     }
 
+    pub fn update_carry_forward_policy(
+        ctx: Context<UpdateCarryForwardPolicy>,
+        enabled: bool,
+        min_payout: u64,
+        decay_bps: u16,
+    ) -> Result<()> {
+        require!(decay_bps <= MAX_BASIS_POINTS, ProgramError::InvalidAmount);
+        ctx.accounts.process(enabled, min_payout, decay_bps)
+    }
+
+    pub fn update_emission_schedule(
+        ctx: Context<UpdateEmissionSchedule>,
+        per_period: u64,
+        decay_bps: u16,
+        decay_interval: u16,
+        total_cap: u64,
+    ) -> Result<()> {
+        require!(decay_bps <= MAX_BASIS_POINTS, ProgramError::InvalidAmount);
+        ctx.accounts.process(per_period, decay_bps, decay_interval, total_cap)
+    }
+
     pub fn distribute_tokens(
         ctx: Context<DistributeTokens>,
-        vault_authority_bump: u8,
+        _period: u16,
+    ) -> Result<()> {
+        ctx.accounts.process(&ctx.bumps)
+    }
+
+    pub fn update_payout_cap(
+        ctx: Context<UpdatePayoutCap>,
+        max_share_bps: u16,
+        redistribute_excess: bool,
+    ) -> Result<()> {
+        require!(max_share_bps <= MAX_BASIS_POINTS, ProgramError::InvalidAmount);
+        ctx.accounts.process(max_share_bps, redistribute_excess)
+    }
+
+    pub fn publish_merkle_root(
+        ctx: Context<PublishMerkleRoot>,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        num_leaves: u32,
+    ) -> Result<()> {
+        let bitmap_bump = ctx.bumps.claim_bitmap;
+        ctx.accounts.process(merkle_root, total_amount, num_leaves, bitmap_bump)
+    }
+
+    pub fn claim_with_proof(
+        ctx: Context<ClaimWithProof>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-        ctx.accounts.process(vault_authority_bump)
+        ctx.accounts.process(index, amount, proof, &ctx.bumps)
+    }
+
+    pub fn update_vesting_policy(
+        ctx: Context<UpdateVestingPolicy>,
+        cliff: i64,
+        duration: i64,
+        immediate_bps: u16,
+    ) -> Result<()> {
+        validate_vesting_policy(cliff, duration, immediate_bps)?;
+        ctx.accounts.process(cliff, duration, immediate_bps)
+    }
+
+    pub fn withdraw_vested(
+        ctx: Context<WithdrawVested>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn update_claim_window(
+        ctx: Context<UpdateClaimWindow>,
+        claim_window: i64,
+    ) -> Result<()> {
+        require!(claim_window >= 0, ProgramError::InvalidAmount);
+        ctx.accounts.process(claim_window)
+    }
+
+    pub fn sweep_expired_period(
+        ctx: Context<SweepExpiredPeriod>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn register_reward_pool(
+        ctx: Context<RegisterRewardPool>,
+        pool_ratio_bps: u16,
+        max_per_period: u64,
+    ) -> Result<()> {
+        require!(pool_ratio_bps <= MAX_BASIS_POINTS, ProgramError::InvalidAmount);
+        let bump = ctx.bumps.reward_pool;
+        ctx.accounts.process(pool_ratio_bps, max_per_period, bump)
+    }
+
+    pub fn update_reward_pool(
+        ctx: Context<UpdateRewardPool>,
+        is_active: Option<bool>,
+        pool_ratio_bps: Option<u16>,
+        max_per_period: Option<u64>,
+    ) -> Result<()> {
+        if let Some(ratio) = pool_ratio_bps {
+            require!(ratio <= MAX_BASIS_POINTS, ProgramError::InvalidAmount);
+        }
+        ctx.accounts.process(is_active, pool_ratio_bps, max_per_period)
+    }
+
+    pub fn snapshot_reward_pool(
+        ctx: Context<SnapshotRewardPool>,
+        period: u16,
+    ) -> Result<()> {
+        let bump = ctx.bumps.pool_period;
+        ctx.accounts.process(period, bump)
+    }
+
+    pub fn claim_pool_rewards(
+        ctx: Context<ClaimPoolRewards>,
+    ) -> Result<()> {
+        let receipt_bump = ctx.bumps.claim_receipt;
+        ctx.accounts.process(receipt_bump)
+    }
+
+    pub fn claim_all_pool_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimAllPoolRewards<'info>>,
+        period: u16,
+    ) -> Result<()> {
+        ctx.accounts.process(period, ctx.remaining_accounts)
+    }
+
+    pub fn expire_reward_pool_period(
+        ctx: Context<ExpireRewardPoolPeriod>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn initialize_reserve_state(
+        ctx: Context<InitializeReserveState>,
+    ) -> Result<()> {
+        let bump = ctx.bumps.reserve_state;
+        ctx.accounts.process(bump)
+    }
+
+    pub fn reconcile_reserve(
+        ctx: Context<ReconcileReserve>,
+    ) -> Result<()> {
+        ctx.accounts.process()
     }
 
     pub fn process_reserve_transfer(
         ctx: Context<ManageReserve>,
         amount: u64,
-        vault_authority_bump: u8,
     ) -> Result<()> {
         require!(amount > 0, ProgramError::InvalidAmount);
-        ctx.accounts.process_reserve_transfer(amount, vault_authority_bump)
+        ctx.accounts.process_reserve_transfer(amount)
     }
 
     pub fn process_add_to_reserve(
         ctx: Context<ManageReserve>,
         amount: u64,
-        vault_authority_bump: u8,
     ) -> Result<()> {
         require!(amount > 0, ProgramError::InvalidAmount);
-        ctx.accounts.process_add_to_reserve(amount, vault_authority_bump)
+        ctx.accounts.process_add_to_reserve(amount)
     }
 
     pub fn update_reserve_config(
@@ -91,6 +403,70 @@ pub mod aixblock_rewards {
         }
         ctx.accounts.process(new_reserve_ratio, new_monthly_threshold)
     }
+
+    pub fn propose_reserve_withdrawal_policy(
+        ctx: Context<ProposeReserveWithdrawalPolicy>,
+        guardian: Pubkey,
+        delay: i64,
+        limit: u64,
+    ) -> Result<()> {
+        require!(delay >= MIN_RESERVE_WITHDRAWAL_DELAY, ProgramError::InvalidAmount);
+        ctx.accounts.process(guardian, delay, limit)
+    }
+
+    pub fn apply_reserve_withdrawal_policy(
+        ctx: Context<ApplyReserveWithdrawalPolicy>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn cancel_reserve_withdrawal_policy(
+        ctx: Context<CancelReserveWithdrawalPolicy>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn propose_reserve_withdrawal(
+        ctx: Context<ProposeReserveWithdrawal>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ProgramError::InvalidAmount);
+        let bump = ctx.bumps.reserve_withdrawal;
+        ctx.accounts.process(amount, bump)
+    }
+
+    pub fn cancel_reserve_withdrawal(
+        ctx: Context<CancelReserveWithdrawal>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn execute_reserve_withdrawal(
+        ctx: Context<ExecuteReserveWithdrawal>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn propose_reserve_release_policy(
+        ctx: Context<ProposeReserveReleasePolicy>,
+        release_bps: u16,
+        release_cap: u64,
+    ) -> Result<()> {
+        require!(release_bps <= MAX_BASIS_POINTS, ProgramError::InvalidAmount);
+        ctx.accounts.process(release_bps, release_cap)
+    }
+
+    pub fn apply_reserve_release_policy(
+        ctx: Context<ApplyReserveReleasePolicy>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn cancel_reserve_release_policy(
+        ctx: Context<CancelReserveReleasePolicy>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
 }
 
 // Constants for the program
@@ -99,6 +475,9 @@ pub const MIN_POINTS: u64 = 1;
 pub const MAX_IMPACT_SCORE: u8 = 5;
 pub const MONTH_IN_SECONDS: i64 = 30 * 24 * 60 * 60;
 pub const MAX_RESERVE_RATIO: u16 = 10_000;
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+pub const MIN_RESERVE_WITHDRAWAL_DELAY: i64 = 2 * 24 * 60 * 60;
+pub const POOL_CLAIM_WINDOW: i64 = 3 * MONTH_IN_SECONDS;
 
 #[error_code]
 pub enum ProgramError {
@@ -131,6 +510,12 @@ pub enum ProgramError {
 
     #[msg("Reserve calculation error")]
     ReserveCalculationError,
+
+    #[msg("Invalid contribution type name")]
+    InvalidTypeName,
+
+    #[msg("Invalid vesting policy")]
+    InvalidVestingPolicy,
 }
 
 pub fn validate_impact_score(score: u8) -> Result<()> {
@@ -141,6 +526,14 @@ pub fn validate_impact_score(score: u8) -> Result<()> {
     Ok(())
 }
 
+pub fn validate_self_submission_cap(max_impact: u8) -> Result<()> {
+    require!(
+        max_impact <= MAX_IMPACT_SCORE,
+        ProgramError::InvalidImpactScore
+    );
+    Ok(())
+}
+
 pub fn validate_points(points: u64) -> Result<()> {
     require!(
         points >= MIN_POINTS,
@@ -149,10 +542,26 @@ pub fn validate_points(points: u64) -> Result<()> {
     Ok(())
 }
 
+pub fn validate_type_name(name: &str) -> Result<()> {
+    require!(
+        !name.is_empty() && name.len() <= MAX_TYPE_NAME_LEN,
+        ProgramError::InvalidTypeName
+    );
+    Ok(())
+}
+
 pub fn validate_reserve_ratio(ratio: u16) -> Result<()> {
     require!(
         ratio <= MAX_RESERVE_RATIO,
         ProgramError::InvalidReserveRatio
     );
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn validate_vesting_policy(cliff: i64, duration: i64, immediate_bps: u16) -> Result<()> {
+    require!(
+        cliff >= 0 && duration >= 0 && immediate_bps <= MAX_BASIS_POINTS,
+        ProgramError::InvalidVestingPolicy
+    );
+    Ok(())
+}
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::errors::RewardError;
+use crate::state::contribution_type::ContributionTypeDef;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 #[repr(u8)] // <--- THIS IS CRITICAL
@@ -15,13 +17,52 @@ pub enum ContributionType {
     CodeReview,
 }
 
+impl ContributionType {
+    // Legacy variants, pre-registered as type definitions with matching ids
+    pub const ALL: [ContributionType; LEGACY_CONTRIBUTION_TYPE_COUNT] = [
+        ContributionType::Code,
+        ContributionType::Review,
+        ContributionType::Documentation,
+        ContributionType::Community,
+        ContributionType::Other,
+        ContributionType::Testing,
+        ContributionType::BugReport,
+        ContributionType::PullRequest,
+        ContributionType::CodeCommit,
+        ContributionType::CodeReview,
+    ];
+
+    // Type id of the registered definition for this variant
+    pub fn id(&self) -> u8 {
+        self.clone() as u8
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ContributionType::Code => "Code",
+            ContributionType::Review => "Review",
+            ContributionType::Documentation => "Documentation",
+            ContributionType::Community => "Community",
+            ContributionType::Other => "Other",
+            ContributionType::Testing => "Testing",
+            ContributionType::BugReport => "BugReport",
+            ContributionType::PullRequest => "PullRequest",
+            ContributionType::CodeCommit => "CodeCommit",
+            ContributionType::CodeReview => "CodeReview",
+        }
+    }
+}
+
+// Number of legacy enum variants
+pub const LEGACY_CONTRIBUTION_TYPE_COUNT: usize = 10;
+
 #[account]
 pub struct Contribution {
     // Contributor who made this contribution
     pub contributor: Pubkey,
     
-    // Type of contribution
-    pub contribution_type: ContributionType,
+    // Id of the ContributionTypeDef (legacy ContributionType values decode as ids)
+    pub type_id: u8,
     
     // Points awarded for this contribution
     pub points: u64,
@@ -40,33 +81,35 @@ pub struct Contribution {
     
     // Reserved space for future upgrades
     pub bump: u8,
+
+    // Fields below were appended after the initial release; older accounts
+    // are extended by migrate_contribution
+
+    // Points config this contribution was recorded against
+    pub points_config: Pubkey,
+
+    // Impact score (1-5) the points were calculated from
+    pub impact_score: u8,
 }
 
 impl Contribution {
     pub const SPACE: usize = 8 + // discriminator
         32 +    // contributor
-        1 +     // contribution_type (enum)
+        1 +     // type_id
         8 +     // points
         8 +     // timestamp
         32 +    // metadata
         1 +     // is_verified
         2 +     // period
-        1;      // bump
-
-    pub fn calculate_points(&self) -> Result<u64> {
-        let base_points = match self.contribution_type {
-            ContributionType::CodeCommit => 10,
-            ContributionType::PullRequest => 30,
-            ContributionType::CodeReview => 20,
-            ContributionType::Documentation => 15,
-            ContributionType::BugReport => 10,
-            ContributionType::Testing => 15,
-            ContributionType::Code => 10,
-            ContributionType::Review => 20,
-            ContributionType::Community => 5,
-            ContributionType::Other => 5,
-        };
+        1 +     // bump
+        32 +    // points_config
+        1;      // impact_score
 
-        Ok(base_points)
+    pub fn calculate_points(&self, type_def: &ContributionTypeDef) -> Result<u64> {
+        require!(
+            type_def.type_id == self.type_id,
+            RewardError::InvalidContributionType
+        );
+        Ok(type_def.base_points)
     }
 }
\ No newline at end of file
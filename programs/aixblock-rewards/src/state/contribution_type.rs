@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct ContributionTypeDef {
+    // Points config this type is registered under
+    pub points_config: Pubkey,
+
+    // Id referenced by contributions (legacy enum values use 0-9)
+    pub type_id: u8,
+
+    // Display name
+    pub name: String,
+
+    // Base points before the impact multiplier
+    pub base_points: u64,
+
+    // Disabled types cannot be recorded
+    pub is_enabled: bool,
+
+    // Whether contributions wait for moderator verification before counting
+    pub requires_verification: bool,
+
+    // Reserved space for future upgrades
+    pub bump: u8,
+}
+
+impl ContributionTypeDef {
+    pub const SPACE: usize = 8 + // discriminator
+        32 +    // points_config
+        1 +     // type_id
+        4 + MAX_TYPE_NAME_LEN + // name
+        8 +     // base_points
+        1 +     // is_enabled
+        1 +     // requires_verification
+        1;      // bump
+}
+
+pub const MAX_TYPE_NAME_LEN: usize = 32;
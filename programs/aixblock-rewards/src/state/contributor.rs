@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use crate::errors::RewardError;
 
 #[account]
 #[derive(Default)]
@@ -23,9 +25,24 @@ pub struct Contributor {
     
     // Is verified contributor
     pub is_verified: bool,
-    
+
     // Reserved space for future upgrades
     pub bump: u8,
+
+    // Fields below were appended after the initial release; older accounts
+    // are extended by migrate_contributor
+
+    // Period current_month_points were earned in
+    pub points_period: u16,
+
+    // Token account payouts may be sent to besides the authority's ATA
+    pub beneficiary: Pubkey,
+
+    // Points carried into points_period from an unpaid earlier period, after decay
+    pub carried_points: u64,
+
+    // Period the carried points were originally earned in
+    pub carried_from_period: u16,
 }
 
 impl Contributor {
@@ -37,5 +54,62 @@ impl Contributor {
         8 +     // last_claim_time
         4 +     // contribution_count
         1 +     // is_verified
-        1;      // bump
-}
\ No newline at end of file
+        1 +     // bump
+        2 +     // points_period
+        32 +    // beneficiary
+        8 +     // carried_points
+        2;      // carried_from_period
+
+    // Adds verified points to the lifetime and monthly totals. Claimable
+    // points are tracked per period in ContributorPeriodPoints.
+    pub fn credit_points(&mut self, points: u64, period: u16) -> Result<()> {
+        self.start_period(period);
+
+        self.total_points = self.total_points
+            .checked_add(points)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        self.current_month_points = self.current_month_points
+            .checked_add(points)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        Ok(())
+    }
+
+    // Records points carried into `period`; they were already counted in
+    // total_points when first earned
+    pub fn carry_points(&mut self, carried: u64, from_period: u16, period: u16) -> Result<()> {
+        self.start_period(period);
+
+        self.carried_points = self.carried_points
+            .checked_add(carried)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+        self.carried_from_period = from_period;
+
+        Ok(())
+    }
+
+    fn start_period(&mut self, period: u16) {
+        if self.points_period != period {
+            self.current_month_points = 0;
+            self.carried_points = 0;
+            self.points_period = period;
+        }
+    }
+
+    // Payouts may only land in the registered beneficiary account or in the
+    // authority's associated token account for the mint
+    pub fn is_payout_destination(
+        &self,
+        token_account: &Pubkey,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+    ) -> bool {
+        if self.beneficiary != Pubkey::default() && *token_account == self.beneficiary {
+            return true;
+        }
+
+        *token_account
+            == get_associated_token_address_with_program_id(&self.authority, mint, token_program)
+    }
+}
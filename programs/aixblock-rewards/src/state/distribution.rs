@@ -0,0 +1,328 @@
+use anchor_lang::prelude::*;
+use crate::errors::RewardError;
+
+#[account]
+#[derive(Default)]
+pub struct DistributionPeriod {
+    pub period: u16,
+    // Pool snapshot taken at close; claims are paid pro-rata from it
+    pub total_tokens: u64,
+    pub tokens_distributed: u64,
+    pub total_points: u64,
+    // Reward vault balance at close, excluding tokens owed to earlier periods
+    pub vault_balance: u64,
+    // Share of the vault withheld from the pool by the reserve ratio
+    pub reserve_amount: u64,
+    // Withheld share the reserve vault received at close, net of transfer fees
+    pub reserve_transferred: u64,
+    // Reserve tokens released into the pool at close, net of transfer fees
+    pub reserve_released: u64,
+    // Points already paid out
+    pub points_claimed: u64,
+    // Points rolled into a later period instead of being paid
+    pub points_carried: u64,
+    // Rounding remainder swept to the reserve once every point is claimed
+    pub dust_swept: u64,
+    // Root of the (index, claimant, amount) tree when claims are paid by proof
+    pub merkle_root: [u8; 32],
+    // Sum of all leaf amounts in the published tree
+    pub merkle_total_amount: u64,
+    // Claims are rejected after this timestamp (0 = no expiry)
+    pub claim_deadline: i64,
+    // Unclaimed remainder returned to the reserve after the deadline
+    pub unclaimed_swept: u64,
+    // Largest single payout, from the config's max_share_bps (0 = uncapped)
+    pub max_payout: u64,
+    // Whether capped excess goes to the other contributors instead of the
+    // reserve; such periods are paid by proof from an off-chain allocation
+    pub redistribute_excess: bool,
+    // Capped excess sent straight to the reserve
+    pub excess_to_reserve: u64,
+    pub is_completed: bool,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub bump: u8,
+}
+
+impl DistributionPeriod {
+    pub const SPACE: usize = 8 + // discriminator
+        2 +     // period
+        8 +     // total_tokens
+        8 +     // tokens_distributed
+        8 +     // total_points
+        8 +     // vault_balance
+        8 +     // reserve_amount
+        8 +     // reserve_transferred
+        8 +     // reserve_released
+        8 +     // points_claimed
+        8 +     // points_carried
+        8 +     // dust_swept
+        32 +    // merkle_root
+        8 +     // merkle_total_amount
+        8 +     // claim_deadline
+        8 +     // unclaimed_swept
+        8 +     // max_payout
+        1 +     // redistribute_excess
+        8 +     // excess_to_reserve
+        1 +     // is_completed
+        8 +     // start_time
+        8 +     // end_time
+        1;      // bump
+
+    pub fn has_merkle_root(&self) -> bool {
+        self.merkle_root != [0u8; 32]
+    }
+
+    // Every point has either been paid or carried forward
+    pub fn is_fully_settled(&self) -> bool {
+        self.points_claimed.saturating_add(self.points_carried) >= self.total_points
+    }
+
+    // Payout `points` would receive from the pool snapshot
+    pub fn payout_for_points(&self, points: u64) -> Result<u64> {
+        if self.total_points == 0 || points == 0 {
+            return Ok(0);
+        }
+
+        let amount = (self.total_tokens as u128)
+            .checked_mul(points as u128)
+            .ok_or(RewardError::InvalidPointsCalculation)?
+            .checked_div(self.total_points as u128)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        Ok(amount as u64)
+    }
+
+    // Tokens of the pool that are neither paid out nor sent to the reserve
+    pub fn unsettled_tokens(&self) -> u64 {
+        self.total_tokens
+            .saturating_sub(self.tokens_distributed)
+            .saturating_sub(self.excess_to_reserve)
+    }
+
+    // Redistributing capped excess needs every contributor's points, so
+    // the allocation is computed off-chain and published as a Merkle root.
+    // The config authority is trusted with that allocation: on-chain each
+    // leaf is only bounded by max_payout and the tree total by the pool.
+    pub fn requires_merkle_distribution(&self) -> bool {
+        self.redistribute_excess && self.max_payout > 0
+    }
+
+    // Applies the per-contributor cap to a claim of `points` and returns
+    // (payout, excess). The result depends only on the claimant's points,
+    // never on who claimed before.
+    pub fn apply_payout_cap(&self, points: u64) -> Result<(u64, u64)> {
+        let payout = self.payout_for_points(points)?;
+
+        if self.max_payout == 0 || payout <= self.max_payout {
+            return Ok((payout, 0));
+        }
+
+        Ok((self.max_payout, payout - self.max_payout))
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.claim_deadline != 0 && now > self.claim_deadline
+    }
+
+    // Rejects claims once the period is settled or its window has passed
+    pub fn validate_claimable(&self, now: i64) -> Result<()> {
+        require!(!self.is_completed, RewardError::DistributionAlreadyProcessed);
+        require!(!self.is_expired(now), RewardError::ClaimWindowExpired);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct ClaimReceipt {
+    // Distribution period the claim was paid from
+    pub distribution_period: Pubkey,
+
+    // Contributor who claimed
+    pub contributor: Pubkey,
+
+    // Contributor points the payout was based on
+    pub points: u64,
+
+    // Tokens paid out
+    pub amount: u64,
+
+    // Claim timestamp
+    pub claimed_at: i64,
+
+    // Reserved space for future upgrades
+    pub bump: u8,
+}
+
+impl ClaimReceipt {
+    pub const SPACE: usize = 8 + // discriminator
+        32 +    // distribution_period
+        32 +    // contributor
+        8 +     // points
+        8 +     // amount
+        8 +     // claimed_at
+        1;      // bump
+}
+
+// Largest tree whose bitmap still fits in a single account allocation
+pub const MAX_MERKLE_LEAVES: u32 = 80_000;
+
+#[account]
+pub struct ClaimBitmap {
+    // Distribution period the bitmap guards
+    pub distribution_period: Pubkey,
+
+    // Number of leaves in the published tree
+    pub num_leaves: u32,
+
+    // Leaves claimed so far
+    pub num_claimed: u32,
+
+    // One bit per leaf index, set once claimed
+    pub bits: Vec<u8>,
+
+    pub bump: u8,
+}
+
+impl ClaimBitmap {
+    pub fn space(num_leaves: u32) -> usize {
+        8 +     // discriminator
+        32 +    // distribution_period
+        4 +     // num_leaves
+        4 +     // num_claimed
+        4 + Self::bitmap_len(num_leaves) + // bits
+        1       // bump
+    }
+
+    pub fn bitmap_len(num_leaves: u32) -> usize {
+        (num_leaves as usize).div_ceil(8)
+    }
+
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.bits[(index / 8) as usize] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, index: u32) {
+        self.bits[(index / 8) as usize] |= 1 << (index % 8);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reference for the off-chain allocation of a period that redistributes
+    // capped excess: `total_tokens` over every contributor's `points`. Shares
+    // are pro-rata, no share exceeds `max_payout` (0 = uncapped), and what
+    // capped contributors cannot take is spread pro-rata over the rest.
+    // The result does not depend on the order of `points`.
+    fn capped_allocations(total_tokens: u64, max_payout: u64, points: &[u64]) -> Vec<u64> {
+        let mut allocations = vec![0u64; points.len()];
+        let mut remaining_tokens = total_tokens as u128;
+        let mut remaining_points: u128 = points.iter().map(|&p| p as u128).sum();
+
+        // Largest holders first: if the largest remaining share fits under the
+        // cap, every smaller one does too
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        order.sort_by(|&a, &b| points[b].cmp(&points[a]).then(a.cmp(&b)));
+
+        let mut uncapped_from = order.len();
+        for (position, &index) in order.iter().enumerate() {
+            if max_payout == 0 || remaining_points == 0 {
+                uncapped_from = position;
+                break;
+            }
+
+            let share = remaining_tokens * points[index] as u128 / remaining_points;
+            if share < max_payout as u128 {
+                uncapped_from = position;
+                break;
+            }
+
+            allocations[index] = max_payout;
+            remaining_tokens -= max_payout as u128;
+            remaining_points -= points[index] as u128;
+        }
+
+        for &index in &order[uncapped_from..] {
+            allocations[index] = (remaining_tokens * points[index] as u128)
+                .checked_div(remaining_points)
+                .unwrap_or(0) as u64;
+        }
+
+        allocations
+    }
+
+    fn capped_period(total_tokens: u64, total_points: u64, max_payout: u64) -> DistributionPeriod {
+        DistributionPeriod {
+            total_tokens,
+            total_points,
+            max_payout,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn payout_cap_is_independent_of_claim_order() {
+        let period = capped_period(1_000, 1_000, 500);
+
+        assert_eq!(period.apply_payout_cap(800).unwrap(), (500, 300));
+        assert_eq!(period.apply_payout_cap(100).unwrap(), (100, 0));
+
+        // Payouts plus excess always add up to the pro-rata pool
+        let settled: u64 = [800, 100, 100]
+            .iter()
+            .map(|&points| {
+                let (payout, excess) = period.apply_payout_cap(points).unwrap();
+                payout + excess
+            })
+            .sum();
+        assert_eq!(settled, 1_000);
+    }
+
+    #[test]
+    fn allocations_are_pro_rata_without_a_cap() {
+        let allocations = capped_allocations(1_000, 0, &[3, 3, 3]);
+
+        assert_eq!(allocations, vec![333, 333, 333]);
+    }
+
+    #[test]
+    fn capped_excess_is_spread_over_the_rest() {
+        // 5000 bps cap on a 1000 token pool
+        let allocations = capped_allocations(1_000, 500, &[800, 100, 100]);
+
+        assert_eq!(allocations, vec![500, 250, 250]);
+        assert_eq!(allocations.iter().sum::<u64>(), 1_000);
+    }
+
+    #[test]
+    fn redistribution_can_push_others_over_the_cap() {
+        // After the first share is capped the second one exceeds the cap too
+        let allocations = capped_allocations(1_000, 350, &[600, 300, 100]);
+
+        assert_eq!(allocations, vec![350, 350, 300]);
+        assert_eq!(allocations.iter().sum::<u64>(), 1_000);
+    }
+
+    #[test]
+    fn allocations_do_not_depend_on_order() {
+        let points = [50, 700, 125, 125];
+        let allocations = capped_allocations(10_000, 4_000, &points);
+
+        let reversed: Vec<u64> = points.iter().rev().copied().collect();
+        let mut reversed_allocations = capped_allocations(10_000, 4_000, &reversed);
+        reversed_allocations.reverse();
+
+        assert_eq!(allocations, reversed_allocations);
+        assert!(allocations.iter().all(|&amount| amount <= 4_000));
+        assert!(allocations.iter().sum::<u64>() <= 10_000);
+    }
+
+    #[test]
+    fn allocations_stop_at_the_cap_when_everyone_is_capped() {
+        let allocations = capped_allocations(1_000, 300, &[1, 1]);
+
+        assert_eq!(allocations, vec![300, 300]);
+    }
+}
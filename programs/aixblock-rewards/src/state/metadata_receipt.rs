@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct MetadataReceipt {
+    // Points config the artifact was recorded against
+    pub points_config: Pubkey,
+
+    // Contribution that claimed this artifact
+    pub contribution: Pubkey,
+
+    // Id of the ContributionTypeDef
+    pub type_id: u8,
+
+    // Artifact hash (e.g., PR number, commit hash)
+    pub metadata: [u8; 32],
+
+    // Timestamp the artifact was first recorded
+    pub created_at: i64,
+
+    // Reserved space for future upgrades
+    pub bump: u8,
+}
+
+impl MetadataReceipt {
+    pub const SPACE: usize = 8 + // discriminator
+        32 +    // points_config
+        32 +    // contribution
+        1 +     // type_id
+        32 +    // metadata
+        8 +     // created_at
+        1;      // bump
+}
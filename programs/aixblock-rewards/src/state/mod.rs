@@ -1,7 +1,25 @@
 pub mod contributor;
 pub mod contribution;
+pub mod contribution_type;
 pub mod points;
+pub mod moderator;
+pub mod metadata_receipt;
+pub mod period_stats;
+pub mod period_points;
+pub mod distribution;
+pub mod vesting;
+pub mod reward_pool;
+pub mod reserve;
 
 pub use contributor::*;
 pub use contribution::*;
-pub use points::*;
\ No newline at end of file
+pub use contribution_type::*;
+pub use points::*;
+pub use moderator::*;
+pub use metadata_receipt::*;
+pub use period_stats::*;
+pub use period_points::*;
+pub use distribution::*;
+pub use vesting::*;
+pub use reward_pool::*;
+pub use reserve::*;
\ No newline at end of file
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct Moderator {
+    // Points config this role is scoped to
+    pub points_config: Pubkey,
+
+    // Wallet that signs as this moderator
+    pub authority: Pubkey,
+
+    // Bitmask of granted permissions
+    pub permissions: u8,
+
+    // Suspended moderators keep their PDA but lose all permissions
+    pub is_suspended: bool,
+
+    // Timestamp the role was granted
+    pub added_at: i64,
+
+    // Reserved space for future upgrades
+    pub bump: u8,
+}
+
+impl Moderator {
+    pub const SPACE: usize = 8 + // discriminator
+        32 +    // points_config
+        32 +    // authority
+        1 +     // permissions
+        1 +     // is_suspended
+        8 +     // added_at
+        1;      // bump
+
+    pub fn has_permission(&self, permission: u8) -> bool {
+        !self.is_suspended && self.permissions & permission == permission
+    }
+}
+
+// Moderator permission bits
+pub const PERMISSION_VERIFY_CONTRIBUTIONS: u8 = 1 << 0;
+pub const PERMISSION_ADJUST_IMPACT: u8 = 1 << 1;
+pub const PERMISSION_MANAGE_RESERVE: u8 = 1 << 2;
+pub const PERMISSION_CLOSE_PERIOD: u8 = 1 << 3;
+pub const ALL_PERMISSIONS: u8 = PERMISSION_VERIFY_CONTRIBUTIONS
+    | PERMISSION_ADJUST_IMPACT
+    | PERMISSION_MANAGE_RESERVE
+    | PERMISSION_CLOSE_PERIOD;
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::errors::RewardError;
+
+#[account]
+#[derive(Default)]
+pub struct ContributorPeriodPoints {
+    // Points config the points were earned under
+    pub points_config: Pubkey,
+
+    // Contributor the points belong to
+    pub contributor: Pubkey,
+
+    // Distribution period (month/year)
+    pub period: u16,
+
+    // Verified points credited in the period, including carried-in points
+    pub points: u64,
+
+    // Points carried in from an unpaid earlier period
+    pub carried_in: u64,
+
+    // Period the carried points were originally earned in
+    pub carried_from_period: u16,
+
+    // Points moved out to a later period; they are no longer claimable here
+    pub carried_out: u64,
+
+    // Highest scoring-agent attestation nonce consumed in the period
+    pub last_attestation_nonce: u64,
+
+    // Reserved space for future upgrades
+    pub bump: u8,
+}
+
+impl ContributorPeriodPoints {
+    pub const SPACE: usize = 8 + // discriminator
+        32 +    // points_config
+        32 +    // contributor
+        2 +     // period
+        8 +     // points
+        8 +     // carried_in
+        2 +     // carried_from_period
+        8 +     // carried_out
+        8 +     // last_attestation_nonce
+        1;      // bump
+
+    // Points the period's pools can still pay out against
+    pub fn claimable(&self) -> u64 {
+        self.points.saturating_sub(self.carried_out)
+    }
+
+    pub fn credit(&mut self, points: u64) -> Result<()> {
+        self.points = self.points
+            .checked_add(points)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+        Ok(())
+    }
+
+    // Adds points carried in from `from_period`
+    pub fn carry_in(&mut self, from_period: u16, carried: u64) -> Result<()> {
+        self.credit(carried)?;
+        self.carried_in = self.carried_in
+            .checked_add(carried)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+        self.carried_from_period = from_period;
+        Ok(())
+    }
+
+    // Moves every claimable point out of the period and returns how many
+    pub fn carry_out(&mut self) -> Result<u64> {
+        let points = self.claimable();
+        require!(points > 0, RewardError::NothingToClaim);
+
+        self.carried_out = self.points;
+        Ok(points)
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::errors::RewardError;
+
+#[account]
+#[derive(Default)]
+pub struct ContributorPeriodStats {
+    // Points config the stats are tracked against
+    pub points_config: Pubkey,
+
+    // Contributor the stats belong to
+    pub contributor: Pubkey,
+
+    // Distribution period (month/year)
+    pub period: u16,
+
+    // Id of the ContributionTypeDef
+    pub type_id: u8,
+
+    // Points awarded for this type in the period
+    pub points: u64,
+
+    // Reserved space for future upgrades
+    pub bump: u8,
+}
+
+impl ContributorPeriodStats {
+    pub const SPACE: usize = 8 + // discriminator
+        32 +    // points_config
+        32 +    // contributor
+        2 +     // period
+        1 +     // type_id
+        8 +     // points
+        1;      // bump
+
+    // Adds points up to the monthly cap and returns the amount actually awarded
+    pub fn award(&mut self, points: u64, cap: u64) -> Result<u64> {
+        let remaining = cap.saturating_sub(self.points);
+        require!(remaining > 0, RewardError::MonthlyTypeCapReached);
+
+        let awarded = points.min(remaining);
+        self.points = self.points
+            .checked_add(awarded)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        Ok(awarded)
+    }
+
+    // Returns points from a contribution that no longer counts
+    pub fn release(&mut self, points: u64) {
+        self.points = self.points.saturating_sub(points);
+    }
+}
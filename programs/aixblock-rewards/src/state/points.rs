@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 use crate::errors::RewardError;
-use crate::state::ContributionType;
+use crate::state::{
+    contribution::LEGACY_CONTRIBUTION_TYPE_COUNT,
+    contribution_type::ContributionTypeDef,
+};
 
 #[account]
 #[derive(Default)]
@@ -28,6 +31,88 @@ pub struct PointsConfig {
     
     // Reserved space for future upgrades
     pub bump: u8,
+
+    // Fields below were appended after the initial release; older accounts
+    // are extended by migrate_points_config
+
+    // Highest impact score a contributor may record for themselves (0 = disallowed)
+    pub self_submission_max_impact: u8,
+
+    // Trusted recorder/agent keys that may record on behalf of contributors
+    pub recorders: Vec<Pubkey>,
+
+    // Off-chain scoring agent whose Ed25519 attestations are accepted
+    pub attestation_agent: Pubkey,
+
+    // Seconds after a payout before any locked tokens unlock
+    pub vesting_cliff: i64,
+
+    // Seconds over which locked tokens unlock linearly (0 with no cliff = no vesting)
+    pub vesting_duration: i64,
+
+    // Share of each payout released immediately (basis points)
+    pub vesting_immediate_bps: u16,
+
+    // Seconds after close during which a period can be claimed (0 = no expiry)
+    pub claim_window: i64,
+
+    // Roll unpaid sub-threshold or sub-minimum points into the next period
+    pub carry_forward_enabled: bool,
+
+    // Payouts below this token amount are carried forward instead
+    pub carry_forward_min_payout: u64,
+
+    // Share of carried points lost on each carry (basis points)
+    pub carry_forward_decay_bps: u16,
+
+    // Tokens released per period by the emission schedule (0 = size pools from the vault)
+    pub emission_per_period: u64,
+
+    // Cut applied to the emission every decay interval (basis points, 5000 = halving)
+    pub emission_decay_bps: u16,
+
+    // Periods between emission decays (0 = no decay)
+    pub emission_decay_interval: u16,
+
+    // Hard cap on tokens ever emitted by the schedule (0 = uncapped)
+    pub emission_cap: u64,
+
+    // Period the schedule's decay is counted from
+    pub emission_start_period: u16,
+
+    // Tokens emitted by the schedule so far
+    pub total_emitted: u64,
+
+    // Reward vault tokens owed to closed periods and not yet paid or swept
+    pub committed_rewards: u64,
+
+    // Largest share of a period pool one contributor can receive (basis points, 0 = uncapped)
+    pub max_share_bps: u16,
+
+    // Hand capped excess to the other contributors instead of the reserve;
+    // capped periods are then paid through a Merkle root
+    pub redistribute_excess: bool,
+
+    // Share of the reserve released into a period that meets the threshold (basis points, 0 = off)
+    pub reserve_release_bps: u16,
+
+    // Upper bound on a single release (0 = no cap)
+    pub reserve_release_cap: u64,
+
+    // Mint every program vault holds, set by initialize_vaults
+    pub reward_mint: Pubkey,
+
+    // Program vaults, each owned by its authority PDA
+    pub reward_vault: Pubkey,
+    pub reserve_vault: Pubkey,
+    pub distribution_vault: Pubkey,
+    pub vesting_vault: Pubkey,
+
+    // Canonical bumps of the vault authority PDAs
+    pub vault_authority_bump: u8,
+    pub reserve_authority_bump: u8,
+    pub distribution_authority_bump: u8,
+    pub vesting_authority_bump: u8,
 }
 
 impl PointsConfig {
@@ -39,7 +124,37 @@ impl PointsConfig {
         2 +     // current_period
         8 +     // period_total_points
         8 +     // last_calculation_time
-        1;      // bump
+        1 +     // bump
+        1 +     // self_submission_max_impact
+        4 + 32 * MAX_RECORDERS + // recorders
+        32 +    // attestation_agent
+        8 +     // vesting_cliff
+        8 +     // vesting_duration
+        2 +     // vesting_immediate_bps
+        8 +     // claim_window
+        1 +     // carry_forward_enabled
+        8 +     // carry_forward_min_payout
+        2 +     // carry_forward_decay_bps
+        8 +     // emission_per_period
+        2 +     // emission_decay_bps
+        2 +     // emission_decay_interval
+        8 +     // emission_cap
+        2 +     // emission_start_period
+        8 +     // total_emitted
+        8 +     // committed_rewards
+        2 +     // max_share_bps
+        1 +     // redistribute_excess
+        2 +     // reserve_release_bps
+        8 +     // reserve_release_cap
+        32 +    // reward_mint
+        32 +    // reward_vault
+        32 +    // reserve_vault
+        32 +    // distribution_vault
+        32 +    // vesting_vault
+        1 +     // vault_authority_bump
+        1 +     // reserve_authority_bump
+        1 +     // distribution_authority_bump
+        1;      // vesting_authority_bump
 
     pub fn calculate_distribution_amount(
         &self,
@@ -65,35 +180,91 @@ impl PointsConfig {
         }
     }
 
+    pub fn is_emission_scheduled(&self) -> bool {
+        self.emission_per_period > 0
+    }
+
+    // Budget the emission schedule releases for `period`, after decay and
+    // limited to what is left under the total cap
+    pub fn emission_budget(&self, period: u16) -> Result<u64> {
+        let mut budget = self.emission_per_period;
+
+        if self.emission_decay_interval > 0 && self.emission_decay_bps > 0 {
+            let decays = period.saturating_sub(self.emission_start_period) / self.emission_decay_interval;
+            let retained = 10000u128.saturating_sub(self.emission_decay_bps as u128);
+            let factor = pow_bps(retained, decays as u32);
+
+            budget = (budget as u128)
+                .checked_mul(factor)
+                .ok_or(RewardError::InvalidPointsCalculation)?
+                .checked_div(EMISSION_FACTOR_SCALE)
+                .ok_or(RewardError::InvalidPointsCalculation)? as u64;
+        }
+
+        if self.emission_cap > 0 {
+            budget = budget.min(self.emission_cap.saturating_sub(self.total_emitted));
+        }
+
+        Ok(budget)
+    }
+
+    pub fn has_vaults(&self) -> bool {
+        self.reward_mint != Pubkey::default()
+    }
+
+    // Reward vault tokens not yet owed to a closed period
+    pub fn free_reward_balance(&self, vault_balance: u64) -> u64 {
+        vault_balance.saturating_sub(self.committed_rewards)
+    }
+
+    // Marks tokens as owed to a closing period until they are paid or swept
+    pub fn commit_rewards(&mut self, amount: u64) -> Result<()> {
+        self.committed_rewards = self.committed_rewards
+            .checked_add(amount)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+        Ok(())
+    }
+
+    pub fn release_committed_rewards(&mut self, amount: u64) {
+        self.committed_rewards = self.committed_rewards.saturating_sub(amount);
+    }
+
+    // Reserve tokens released into a closing period that met the threshold
+    pub fn reserve_release_amount(&self, total_points: u64, reserve_balance: u64) -> Result<u64> {
+        if self.reserve_release_bps == 0 || total_points < self.monthly_threshold {
+            return Ok(0);
+        }
+
+        let release = (reserve_balance as u128)
+            .checked_mul(self.reserve_release_bps as u128)
+            .ok_or(RewardError::ReserveCalculationError)?
+            .checked_div(10000)
+            .ok_or(RewardError::ReserveCalculationError)? as u64;
+
+        if self.reserve_release_cap > 0 {
+            Ok(release.min(self.reserve_release_cap))
+        } else {
+            Ok(release)
+        }
+    }
+
     pub fn calculate_contribution_points(
         &self,
-        contribution_type: &ContributionType,
+        type_def: &ContributionTypeDef,
         impact_score: u8,
     ) -> Result<u64> {
-        // Base points for each contribution type
-        let base_points: u64 = match contribution_type {
-            ContributionType::Code => 10,
-            ContributionType::Review => 20,
-            ContributionType::Documentation => 15,
-            ContributionType::Community => 5,
-            ContributionType::Other => 5,
-            ContributionType::Testing => 15,
-            ContributionType::BugReport => 10,
-            ContributionType::PullRequest => 30,
-            ContributionType::CodeCommit => 10,
-            ContributionType::CodeReview => 20,
-        };
+        let base_points = type_def.base_points;
 
         // Impact multiplier (1-5 scale)
-        let impact_multiplier = impact_score.max(1).min(5) as u64;
+        let impact_multiplier = impact_score.clamp(1, 5) as u64;
 
-        // Calculate total points
+        // Calculate total points; the monthly per-type cap is applied
+        // against the contributor's ContributorPeriodStats
         let total_points = base_points
             .checked_mul(impact_multiplier)
             .ok_or(RewardError::InvalidPointsCalculation)?;
 
-        // Ensure not exceeding max points per type
-        Ok(total_points.min(self.max_points_per_type))
+        Ok(total_points)
     }
 
     pub fn validate_monthly_distribution(
@@ -108,28 +279,66 @@ impl PointsConfig {
             return Err(RewardError::DistributionPeriodNotEnded.into());
         }
 
-        // Check if total points meet threshold for full distribution
-        if self.period_total_points < self.monthly_threshold {
-            msg!("Below monthly threshold, applying reserve ratio");
+        Ok(true)
+    }
+
+    pub fn is_recorder(&self, key: &Pubkey) -> bool {
+        self.recorders.contains(key)
+    }
+
+    // Checks the recorder against the self-submission policy and the trusted
+    // recorder list, returning the impact score to apply
+    pub fn authorize_recorder(
+        &self,
+        recorder: &Pubkey,
+        contributor_authority: &Pubkey,
+        impact_score: u8,
+    ) -> Result<u8> {
+        if recorder == contributor_authority {
+            require!(
+                self.self_submission_max_impact > 0,
+                RewardError::Unauthorized
+            );
+            return Ok(impact_score.min(self.self_submission_max_impact));
         }
 
-        Ok(true)
+        require!(self.is_recorder(recorder), RewardError::Unauthorized);
+        Ok(impact_score)
     }
 
-    pub fn update_period_points(&mut self, points: u64) -> Result<()> {
-        self.period_total_points = self.period_total_points
-            .checked_add(points)
-            .ok_or(RewardError::InvalidPointsCalculation)?;
-        Ok(())
+    pub fn is_vesting_enabled(&self) -> bool {
+        self.vesting_cliff > 0 || self.vesting_duration > 0
     }
 
-    pub fn calculate_reserve_amount(&self, total_points: u64) -> Result<u64> {
-        self.calculate_distribution_amount(total_points, self.period_total_points)
+    // Splits a payout into the part released now and the part locked in escrow
+    pub fn split_vested_payout(&self, amount: u64) -> Result<(u64, u64)> {
+        if !self.is_vesting_enabled() {
+            return Ok((amount, 0));
+        }
+
+        let immediate = (amount as u128)
+            .checked_mul(self.vesting_immediate_bps as u128)
+            .ok_or(RewardError::InvalidPointsCalculation)?
+            .checked_div(10000)
+            .ok_or(RewardError::InvalidPointsCalculation)? as u64;
+
+        Ok((immediate, amount - immediate))
     }
 
-    pub fn update_reserve(&mut self, amount: u64) -> Result<()> {
+    // Points that survive a carry into the next period
+    pub fn apply_carry_forward_decay(&self, points: u64) -> Result<u64> {
+        let kept = (points as u128)
+            .checked_mul(10000u128.saturating_sub(self.carry_forward_decay_bps as u128))
+            .ok_or(RewardError::InvalidPointsCalculation)?
+            .checked_div(10000)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        Ok(kept as u64)
+    }
+
+    pub fn update_period_points(&mut self, points: u64) -> Result<()> {
         self.period_total_points = self.period_total_points
-            .checked_add(amount)
+            .checked_add(points)
             .ok_or(RewardError::InvalidPointsCalculation)?;
         Ok(())
     }
@@ -138,4 +347,119 @@ impl PointsConfig {
 // Constants for point calculations
 pub const MINIMUM_MONTHLY_THRESHOLD: u64 = 500;  // Minimum points needed for full distribution
 pub const DEFAULT_RESERVE_RATIO: u16 = 5000;     // 50% in basis points
-pub const MAX_POINTS_PER_TYPE: u64 = 1000;       // Maximum points per contribution type
\ No newline at end of file
+pub const MAX_POINTS_PER_TYPE: u64 = 1000;       // Maximum points per contribution type
+pub const MAX_RECORDERS: usize = 10;             // Maximum trusted recorder keys per config
+
+// Base points for the pre-registered legacy types, indexed by ContributionType
+pub const DEFAULT_BASE_POINTS: [u64; LEGACY_CONTRIBUTION_TYPE_COUNT] = [
+    10, // Code
+    20, // Review
+    15, // Documentation
+    5,  // Community
+    5,  // Other
+    15, // Testing
+    10, // BugReport
+    30, // PullRequest
+    10, // CodeCommit
+    20, // CodeReview
+];
+
+// Fixed-point scale for compounded emission decay factors
+const EMISSION_FACTOR_SCALE: u128 = 1_000_000_000_000_000_000;
+
+// (bps / 10000) ^ exp in EMISSION_FACTOR_SCALE fixed point, by squaring
+fn pow_bps(bps: u128, mut exp: u32) -> u128 {
+    let mut base = bps * (EMISSION_FACTOR_SCALE / 10000);
+    let mut result = EMISSION_FACTOR_SCALE;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base / EMISSION_FACTOR_SCALE;
+        }
+        base = base * base / EMISSION_FACTOR_SCALE;
+        exp >>= 1;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn committed_rewards_are_not_pooled_twice() {
+        let mut config = PointsConfig::default();
+        let vault_balance = 1_000;
+
+        // The first period takes the whole free balance
+        let first_pool = config.free_reward_balance(vault_balance);
+        config.commit_rewards(first_pool).unwrap();
+        assert_eq!(first_pool, 1_000);
+
+        // Nothing has been claimed yet, so the next period gets only new deposits
+        let vault_balance = vault_balance + 250;
+        let second_pool = config.free_reward_balance(vault_balance);
+        config.commit_rewards(second_pool).unwrap();
+        assert_eq!(second_pool, 250);
+        assert_eq!(config.committed_rewards, vault_balance);
+
+        // Paying a claim lowers the vault and the commitment together
+        let vault_balance = vault_balance - 400;
+        config.release_committed_rewards(400);
+        assert_eq!(config.free_reward_balance(vault_balance), 0);
+    }
+
+    fn scheduled_config(per_period: u64, decay_bps: u16, decay_interval: u16) -> PointsConfig {
+        PointsConfig {
+            emission_per_period: per_period,
+            emission_decay_bps: decay_bps,
+            emission_decay_interval: decay_interval,
+            emission_start_period: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pow_bps_compounds_the_retained_share() {
+        assert_eq!(pow_bps(10000, 7), EMISSION_FACTOR_SCALE);
+        assert_eq!(pow_bps(5000, 0), EMISSION_FACTOR_SCALE);
+        assert_eq!(pow_bps(5000, 3), EMISSION_FACTOR_SCALE / 8);
+        assert_eq!(pow_bps(9000, 2), EMISSION_FACTOR_SCALE / 10000 * 8100);
+    }
+
+    #[test]
+    fn emission_halves_every_interval_at_5000_bps() {
+        let config = scheduled_config(1_000_000, 5000, 3);
+
+        assert_eq!(config.emission_budget(1).unwrap(), 1_000_000);
+        assert_eq!(config.emission_budget(3).unwrap(), 1_000_000);
+        assert_eq!(config.emission_budget(4).unwrap(), 500_000);
+        assert_eq!(config.emission_budget(7).unwrap(), 250_000);
+        assert_eq!(config.emission_budget(10).unwrap(), 125_000);
+    }
+
+    #[test]
+    fn emission_decays_after_n_intervals() {
+        let config = scheduled_config(1_000_000, 1000, 1);
+
+        // 0.9 ^ 5 of the base emission
+        assert_eq!(config.emission_budget(6).unwrap(), 590_490);
+
+        // Without an interval there is no decay
+        let flat = scheduled_config(1_000_000, 1000, 0);
+        assert_eq!(flat.emission_budget(50).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn emission_stops_at_the_cap() {
+        let mut config = scheduled_config(1_000, 0, 0);
+        config.emission_cap = 2_500;
+
+        config.total_emitted = 2_000;
+        assert_eq!(config.emission_budget(3).unwrap(), 500);
+
+        config.total_emitted = 2_500;
+        assert_eq!(config.emission_budget(4).unwrap(), 0);
+    }
+}
@@ -0,0 +1,274 @@
+use anchor_lang::prelude::*;
+use crate::errors::RewardError;
+
+// Number of recent periods whose reserve contributions are kept on-chain
+pub const MAX_RESERVE_HISTORY: usize = 24;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ReservePeriodContribution {
+    pub period: u16,
+    pub amount: u64,
+}
+
+impl ReservePeriodContribution {
+    pub const SPACE: usize = 2 + 8;
+}
+
+#[account]
+pub struct ReserveState {
+    // Points config the reserve belongs to
+    pub points_config: Pubkey,
+
+    // Tokens the program has moved into the reserve vault and not out again
+    pub balance: u64,
+
+    // Lifetime tokens moved into the reserve
+    pub total_inflows: u64,
+
+    // Lifetime tokens moved out of the reserve
+    pub total_outflows: u64,
+
+    // Inflows per period for the most recent periods, oldest first
+    pub period_contributions: Vec<ReservePeriodContribution>,
+
+    // Second key that may cancel pending withdrawals (default = none)
+    pub guardian: Pubkey,
+
+    // Seconds between proposing and executing a withdrawal
+    pub withdrawal_delay: i64,
+
+    // Most tokens withdrawals may take out per period (0 = withdrawals disabled)
+    pub withdrawal_limit: u64,
+
+    // Period the withdrawn counter belongs to
+    pub withdrawal_period: u16,
+
+    // Tokens withdrawn during withdrawal_period
+    pub withdrawn_in_period: u64,
+
+    // Id assigned to the next proposed withdrawal
+    pub next_withdrawal_id: u64,
+
+    // Withdrawal policy waiting out the timelock
+    pub pending_guardian: Pubkey,
+    pub pending_withdrawal_delay: i64,
+    pub pending_withdrawal_limit: u64,
+
+    // When the pending policy may be applied (0 = no policy pending)
+    pub policy_effective_at: i64,
+
+    // Reserve release policy waiting out the same timelock
+    pub pending_release_bps: u16,
+    pub pending_release_cap: u64,
+
+    // When the pending release policy may be applied (0 = none pending)
+    pub release_policy_effective_at: i64,
+
+    // PDA bump
+    pub bump: u8,
+}
+
+impl ReserveState {
+    pub const SPACE: usize = 8 + // discriminator
+        32 +    // points_config
+        8 +     // balance
+        8 +     // total_inflows
+        8 +     // total_outflows
+        4 + ReservePeriodContribution::SPACE * MAX_RESERVE_HISTORY + // period_contributions
+        32 +    // guardian
+        8 +     // withdrawal_delay
+        8 +     // withdrawal_limit
+        2 +     // withdrawal_period
+        8 +     // withdrawn_in_period
+        8 +     // next_withdrawal_id
+        32 +    // pending_guardian
+        8 +     // pending_withdrawal_delay
+        8 +     // pending_withdrawal_limit
+        8 +     // policy_effective_at
+        2 +     // pending_release_bps
+        8 +     // pending_release_cap
+        8 +     // release_policy_effective_at
+        1;      // bump
+
+    // Books tokens that reached the reserve on behalf of `period` and
+    // returns the (before, after) balance
+    pub fn record_inflow(&mut self, amount: u64, period: u16) -> Result<(u64, u64)> {
+        let before = self.balance;
+
+        self.balance = self.balance
+            .checked_add(amount)
+            .ok_or(RewardError::ReserveCalculationError)?;
+        self.total_inflows = self.total_inflows
+            .checked_add(amount)
+            .ok_or(RewardError::ReserveCalculationError)?;
+
+        match self.period_contributions.iter_mut().find(|entry| entry.period == period) {
+            Some(entry) => {
+                entry.amount = entry.amount
+                    .checked_add(amount)
+                    .ok_or(RewardError::ReserveCalculationError)?;
+            }
+            None => {
+                if self.period_contributions.len() == MAX_RESERVE_HISTORY {
+                    self.period_contributions.remove(0);
+                }
+                self.period_contributions.push(ReservePeriodContribution { period, amount });
+            }
+        }
+
+        Ok((before, self.balance))
+    }
+
+    // Books tokens that left the reserve and returns the (before, after) balance
+    pub fn record_outflow(&mut self, amount: u64) -> Result<(u64, u64)> {
+        let before = self.balance;
+
+        self.balance = self.balance
+            .checked_sub(amount)
+            .ok_or(RewardError::InsufficientBalance)?;
+        self.total_outflows = self.total_outflows
+            .checked_add(amount)
+            .ok_or(RewardError::ReserveCalculationError)?;
+
+        Ok((before, self.balance))
+    }
+
+    // Counts a withdrawal against the limit of `period`
+    pub fn record_withdrawal(&mut self, amount: u64, period: u16) -> Result<()> {
+        if self.withdrawal_period != period {
+            self.withdrawal_period = period;
+            self.withdrawn_in_period = 0;
+        }
+
+        let withdrawn = self.withdrawn_in_period
+            .checked_add(amount)
+            .ok_or(RewardError::ReserveCalculationError)?;
+        require!(
+            withdrawn <= self.withdrawal_limit,
+            RewardError::WithdrawalLimitExceeded
+        );
+
+        self.withdrawn_in_period = withdrawn;
+        Ok(())
+    }
+
+    pub fn has_pending_policy(&self) -> bool {
+        self.policy_effective_at != 0
+    }
+
+    // Queues a withdrawal policy behind the current withdrawal delay and
+    // returns when it may be applied
+    pub fn propose_policy(&mut self, guardian: Pubkey, delay: i64, limit: u64, now: i64) -> Result<i64> {
+        let effective_at = now
+            .checked_add(self.withdrawal_delay)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        self.pending_guardian = guardian;
+        self.pending_withdrawal_delay = delay;
+        self.pending_withdrawal_limit = limit;
+        self.policy_effective_at = effective_at;
+
+        Ok(effective_at)
+    }
+
+    pub fn apply_policy(&mut self, now: i64) -> Result<()> {
+        require!(self.has_pending_policy(), RewardError::PolicyChangeNotPending);
+        require!(now >= self.policy_effective_at, RewardError::PolicyChangeTimelocked);
+
+        self.guardian = self.pending_guardian;
+        self.withdrawal_delay = self.pending_withdrawal_delay;
+        self.withdrawal_limit = self.pending_withdrawal_limit;
+        self.clear_pending_policy();
+
+        Ok(())
+    }
+
+    pub fn clear_pending_policy(&mut self) {
+        self.pending_guardian = Pubkey::default();
+        self.pending_withdrawal_delay = 0;
+        self.pending_withdrawal_limit = 0;
+        self.policy_effective_at = 0;
+    }
+
+    pub fn has_pending_release_policy(&self) -> bool {
+        self.release_policy_effective_at != 0
+    }
+
+    // Queues a reserve release policy behind the withdrawal delay, since a
+    // release moves reserve tokens out just like a withdrawal does
+    pub fn propose_release_policy(&mut self, release_bps: u16, release_cap: u64, now: i64) -> Result<i64> {
+        let effective_at = now
+            .checked_add(self.withdrawal_delay)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        self.pending_release_bps = release_bps;
+        self.pending_release_cap = release_cap;
+        self.release_policy_effective_at = effective_at;
+
+        Ok(effective_at)
+    }
+
+    // Returns the (release_bps, release_cap) to apply once the timelock has passed
+    pub fn take_release_policy(&mut self, now: i64) -> Result<(u16, u64)> {
+        require!(self.has_pending_release_policy(), RewardError::PolicyChangeNotPending);
+        require!(now >= self.release_policy_effective_at, RewardError::PolicyChangeTimelocked);
+
+        let policy = (self.pending_release_bps, self.pending_release_cap);
+        self.clear_pending_release_policy();
+
+        Ok(policy)
+    }
+
+    pub fn clear_pending_release_policy(&mut self) {
+        self.pending_release_bps = 0;
+        self.pending_release_cap = 0;
+        self.release_policy_effective_at = 0;
+    }
+
+    pub fn can_cancel_withdrawal(&self, signer: &Pubkey, authority: &Pubkey) -> bool {
+        signer == authority || (self.guardian != Pubkey::default() && *signer == self.guardian)
+    }
+}
+
+#[account]
+pub struct ReserveWithdrawal {
+    // Reserve the withdrawal draws from
+    pub reserve_state: Pubkey,
+
+    // Sequential id from the reserve state
+    pub id: u64,
+
+    // Token account the tokens are paid to
+    pub destination: Pubkey,
+
+    pub amount: u64,
+
+    pub proposed_at: i64,
+
+    // Earliest time the withdrawal can be executed
+    pub executable_at: i64,
+
+    pub is_executed: bool,
+
+    pub is_cancelled: bool,
+
+    // PDA bump
+    pub bump: u8,
+}
+
+impl ReserveWithdrawal {
+    pub const SPACE: usize = 8 + // discriminator
+        32 +    // reserve_state
+        8 +     // id
+        32 +    // destination
+        8 +     // amount
+        8 +     // proposed_at
+        8 +     // executable_at
+        1 +     // is_executed
+        1 +     // is_cancelled
+        1;      // bump
+
+    pub fn is_pending(&self) -> bool {
+        !self.is_executed && !self.is_cancelled
+    }
+}
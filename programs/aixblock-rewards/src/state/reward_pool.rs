@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use crate::errors::RewardError;
+
+#[account]
+pub struct RewardPool {
+    // Points config whose ledger the pool pays out against
+    pub points_config: Pubkey,
+
+    // Mint the pool pays in
+    pub mint: Pubkey,
+
+    // Token account holding the pool's funds, owned by this PDA
+    pub vault: Pubkey,
+
+    // Inactive pools take no new period snapshots
+    pub is_active: bool,
+
+    // Share of the free vault balance committed to each period (basis points)
+    pub pool_ratio_bps: u16,
+
+    // Upper bound on each period's pool (0 = no cap)
+    pub max_per_period: u64,
+
+    // Tokens committed to snapshotted periods and not yet paid or released
+    pub outstanding: u64,
+
+    // Reserved space for future upgrades
+    pub bump: u8,
+}
+
+impl RewardPool {
+    pub const SPACE: usize = 8 + // discriminator
+        32 +    // points_config
+        32 +    // mint
+        32 +    // vault
+        1 +     // is_active
+        2 +     // pool_ratio_bps
+        8 +     // max_per_period
+        8 +     // outstanding
+        1;      // bump
+
+    // Pool size for a new period given the vault balance
+    pub fn calculate_period_pool(&self, vault_balance: u64) -> Result<u64> {
+        let free_balance = vault_balance.saturating_sub(self.outstanding);
+
+        let pool = (free_balance as u128)
+            .checked_mul(self.pool_ratio_bps as u128)
+            .ok_or(RewardError::InvalidPointsCalculation)?
+            .checked_div(10000)
+            .ok_or(RewardError::InvalidPointsCalculation)? as u64;
+
+        if self.max_per_period > 0 {
+            Ok(pool.min(self.max_per_period))
+        } else {
+            Ok(pool)
+        }
+    }
+}
+
+#[account]
+pub struct RewardPoolPeriod {
+    // Pool the snapshot belongs to
+    pub reward_pool: Pubkey,
+
+    // Distribution period the snapshot was taken for
+    pub period: u16,
+
+    // Tokens committed to this period
+    pub total_tokens: u64,
+
+    // Tokens paid out so far
+    pub tokens_distributed: u64,
+
+    // Period points from the DistributionPeriod snapshot
+    pub total_points: u64,
+
+    // Claims are rejected after this timestamp (0 = no expiry)
+    pub claim_deadline: i64,
+
+    // Most one contributor may take from the snapshot (0 = no cap)
+    pub max_payout: u64,
+
+    // Capped excess already released back to the pool
+    pub excess_released: u64,
+
+    // Set once the unclaimed remainder was released back to the pool
+    pub is_completed: bool,
+
+    // Reserved space for future upgrades
+    pub bump: u8,
+}
+
+impl RewardPoolPeriod {
+    pub const SPACE: usize = 8 + // discriminator
+        32 +    // reward_pool
+        2 +     // period
+        8 +     // total_tokens
+        8 +     // tokens_distributed
+        8 +     // total_points
+        8 +     // claim_deadline
+        8 +     // max_payout
+        8 +     // excess_released
+        1 +     // is_completed
+        1;      // bump
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.claim_deadline != 0 && now > self.claim_deadline
+    }
+
+    // Books a claim of `points` against the snapshot and returns
+    // (payout, excess). The payout is capped at max_payout and at what the
+    // snapshot still holds; the capped excess goes back to the pool.
+    pub fn claim(&mut self, points: u64, now: i64) -> Result<(u64, u64)> {
+        require!(!self.is_completed, RewardError::DistributionAlreadyProcessed);
+        require!(!self.is_expired(now), RewardError::ClaimWindowExpired);
+        require!(points > 0, RewardError::NothingToClaim);
+
+        let payout = self.payout_for_points(points)?;
+        let (payout, excess) = if self.max_payout > 0 && payout > self.max_payout {
+            (self.max_payout, payout - self.max_payout)
+        } else {
+            (payout, 0)
+        };
+
+        let remaining = self.unsettled_tokens();
+        let amount = payout.min(remaining);
+        let excess = excess.min(remaining - amount);
+        require!(amount > 0, RewardError::InsufficientBalance);
+
+        self.tokens_distributed = self.tokens_distributed
+            .checked_add(amount)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+        self.excess_released = self.excess_released
+            .checked_add(excess)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        Ok((amount, excess))
+    }
+
+    // Tokens of the snapshot that are neither paid out nor released
+    pub fn unsettled_tokens(&self) -> u64 {
+        self.total_tokens
+            .saturating_sub(self.tokens_distributed)
+            .saturating_sub(self.excess_released)
+    }
+
+    pub fn payout_for_points(&self, points: u64) -> Result<u64> {
+        if self.total_points == 0 || points == 0 {
+            return Ok(0);
+        }
+
+        let amount = (self.total_tokens as u128)
+            .checked_mul(points as u128)
+            .ok_or(RewardError::InvalidPointsCalculation)?
+            .checked_div(self.total_points as u128)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        Ok(amount as u64)
+    }
+}
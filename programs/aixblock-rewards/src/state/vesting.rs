@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use crate::errors::RewardError;
+use crate::state::points::PointsConfig;
+
+#[account]
+pub struct VestingEscrow {
+    // Points config the escrow belongs to
+    pub points_config: Pubkey,
+
+    // Contributor the locked tokens belong to
+    pub contributor: Pubkey,
+
+    // Distribution period the payout came from
+    pub period: u16,
+
+    // Tokens locked at payout time
+    pub total_amount: u64,
+
+    // Tokens already withdrawn
+    pub released_amount: u64,
+
+    // Vesting start (payout time)
+    pub start_time: i64,
+
+    // Nothing unlocks before this timestamp
+    pub cliff_time: i64,
+
+    // Everything is unlocked from this timestamp on
+    pub end_time: i64,
+
+    // Reserved space for future upgrades
+    pub bump: u8,
+}
+
+impl VestingEscrow {
+    pub const SPACE: usize = 8 + // discriminator
+        32 +    // points_config
+        32 +    // contributor
+        2 +     // period
+        8 +     // total_amount
+        8 +     // released_amount
+        8 +     // start_time
+        8 +     // cliff_time
+        8 +     // end_time
+        1;      // bump
+
+    // Starts vesting `locked` tokens at `start_time` under the config's policy
+    pub fn open(
+        &mut self,
+        points_config: &Account<PointsConfig>,
+        contributor: Pubkey,
+        period: u16,
+        locked: u64,
+        start_time: i64,
+        bump: u8,
+    ) -> Result<()> {
+        self.points_config = points_config.key();
+        self.contributor = contributor;
+        self.period = period;
+        self.total_amount = locked;
+        self.released_amount = 0;
+        self.start_time = start_time;
+        self.cliff_time = start_time
+            .checked_add(points_config.vesting_cliff)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+        self.end_time = start_time
+            .checked_add(points_config.vesting_duration)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+        self.bump = bump;
+
+        Ok(())
+    }
+
+    // Tokens unlocked at `now`, including those already withdrawn
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        if now < self.cliff_time {
+            return Ok(0);
+        }
+        if now >= self.end_time {
+            return Ok(self.total_amount);
+        }
+
+        let vested = (self.total_amount as u128)
+            .checked_mul((now - self.start_time) as u128)
+            .ok_or(RewardError::InvalidPointsCalculation)?
+            .checked_div((self.end_time - self.start_time) as u128)
+            .ok_or(RewardError::InvalidPointsCalculation)?;
+
+        Ok(vested as u64)
+    }
+
+    pub fn locked_amount(&self) -> u64 {
+        self.total_amount.saturating_sub(self.released_amount)
+    }
+}
@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::errors::RewardError;
+
+// Creates a program-owned PDA for accounts that are passed through
+// `remaining_accounts` and therefore cannot use Anchor's `init`.
+// Returns the canonical bump.
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    target: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> Result<u8> {
+    let (expected, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+    require_keys_eq!(target.key(), expected, RewardError::InvalidAccountAddress);
+    require!(
+        target.data_is_empty() && target.owner == &system_program::ID,
+        RewardError::AccountAlreadyInitialized
+    );
+
+    let bump_seed = [bump];
+    let mut signer_seeds = seeds.to_vec();
+    signer_seeds.push(&bump_seed);
+    let signer_seeds = &[&signer_seeds[..]];
+
+    let rent = Rent::get()?.minimum_balance(space);
+    let current_lamports = target.lamports();
+
+    if current_lamports == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: target.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        )?;
+        return Ok(bump);
+    }
+
+    // Someone pre-funded the address; top it up and take ownership instead
+    let top_up = rent.saturating_sub(current_lamports);
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: target.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: target.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: target.clone(),
+            },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )?;
+
+    Ok(bump)
+}
+
+// Grows a program-owned account to `space` bytes, topping up rent from
+// `payer`. New bytes are zeroed.
+pub fn realloc_account<'info>(
+    payer: &AccountInfo<'info>,
+    target: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let top_up = rent.saturating_sub(target.lamports());
+
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: target.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    target.realloc(space, true)?;
+    Ok(())
+}
+
+// Serializes an account (with discriminator) into freshly created account data
+pub fn write_account<T: AccountSerialize>(target: &AccountInfo, account: &T) -> Result<()> {
+    let mut data = target.try_borrow_mut_data()?;
+    account.try_serialize(&mut &mut data[..])
+}
+
+// Moves tokens out of a PDA-owned vault with `transfer_checked` and returns
+// what `to` actually received, which is less than `amount` when the mint
+// charges a Token-2022 transfer fee
+pub fn transfer_checked_net<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &mut InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    authority: &AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    let balance_before = to.amount;
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: authority.clone(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        mint.decimals,
+    )?;
+
+    to.reload()?;
+    to.amount
+        .checked_sub(balance_before)
+        .ok_or(RewardError::InsufficientBalance.into())
+}